@@ -0,0 +1,10 @@
+// PDA Seeds
+pub const DIVIDEND_SEED: &[u8] = b"dividend";
+pub const DIVIDEND_CLAIM_SEED: &[u8] = b"dividend_claim";
+pub const SPLIT_SEED: &[u8] = b"split";
+pub const SPLIT_CLAIM_SEED: &[u8] = b"split_claim";
+pub const SPLIT_MARKET_SEED: &[u8] = b"split_market";
+pub const VAULT_SEED: &[u8] = b"vault";
+
+// Constraints
+pub const MAX_PROOF_LENGTH: usize = 32; // enough for 2^32 holders
@@ -0,0 +1,52 @@
+use anchor_lang::prelude::*;
+
+#[error_code]
+pub enum CorporateActionError {
+    #[msg("Only the mint authority of the shares can manage their corporate actions")]
+    Unauthorized,
+
+    #[msg("Record date must be in the future and before the claim deadline")]
+    InvalidRecordDate,
+
+    #[msg("Record date has not passed yet")]
+    RecordDateNotReached,
+
+    #[msg("Snapshot has already been recorded")]
+    SnapshotAlreadyRecorded,
+
+    #[msg("Snapshot covers more shares than the mint's supply")]
+    InvalidSnapshot,
+
+    #[msg("Share multiplier must be at least 2")]
+    InvalidShareMultiplier,
+
+    #[msg("Order book does not trade these shares")]
+    InvalidMarket,
+
+    #[msg("Dividend is not payable")]
+    NotPayable,
+
+    #[msg("Split shares are not claimable yet")]
+    NotClaimable,
+
+    #[msg("Claim period has ended")]
+    ClaimPeriodEnded,
+
+    #[msg("Claim period has not ended yet")]
+    ClaimPeriodNotEnded,
+
+    #[msg("Holding is not part of the snapshot")]
+    InvalidProof,
+
+    #[msg("Claims exceed the shares in the snapshot")]
+    SnapshotOverclaimed,
+
+    #[msg("Every market must be restated in the same slot")]
+    MarketsNotRestatedTogether,
+
+    #[msg("Split shares are still unclaimed")]
+    SharesUnclaimed,
+
+    #[msg("Overflow in calculation")]
+    Overflow,
+}
@@ -0,0 +1,134 @@
+use crate::constants::*;
+use crate::error::CorporateActionError;
+use crate::state::*;
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use exchange_core::cpi::accounts::ApplyCorporateAction;
+use exchange_core::program::ExchangeCore;
+use exchange_core::state::OrderBook;
+
+#[derive(Accounts)]
+pub struct ApplySplitToMarket<'info> {
+    #[account(
+        mut,
+        has_one = issuer @ CorporateActionError::Unauthorized,
+        has_one = base_mint,
+    )]
+    pub split: Account<'info, Split>,
+
+    /// Created on the first apply, so a market can never be restated twice
+    #[account(
+        init,
+        payer = issuer,
+        space = SplitMarket::LEN,
+        seeds = [SPLIT_MARKET_SEED, split.key().as_ref(), order_book.key().as_ref()],
+        bump
+    )]
+    pub split_market: Account<'info, SplitMarket>,
+
+    #[account(mut)]
+    pub base_mint: InterfaceAccount<'info, Mint>,
+
+    /// CHECK: Exchange config, validated by exchange_core
+    pub exchange: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        constraint = order_book.base_mint == base_mint.key() @ CorporateActionError::InvalidMarket,
+        has_one = base_vault @ CorporateActionError::InvalidMarket,
+    )]
+    pub order_book: Account<'info, OrderBook>,
+
+    #[account(mut)]
+    pub base_vault: InterfaceAccount<'info, TokenAccount>,
+
+    /// CHECK: Bids slab, validated by exchange_core
+    #[account(mut)]
+    pub bids: UncheckedAccount<'info>,
+
+    /// CHECK: Asks slab, validated by exchange_core
+    #[account(mut)]
+    pub asks: UncheckedAccount<'info>,
+
+    /// CHECK: Event queue, validated by exchange_core
+    pub event_queue: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub issuer: Signer<'info>,
+
+    /// Exchange authority; exchange_core checks it
+    pub authority: Signer<'info>,
+
+    pub exchange_program: Program<'info, ExchangeCore>,
+    pub base_token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Restate a market of the shares at the record date: exchange_core mints the
+/// new shares for everything in its base vault and rescales its orders.
+/// The market stays halted until its accounts have been rescaled.
+///
+/// Every market of the shares must be restated in the slot of the first, which
+/// becomes the snapshot slot, so no shares move between wallets and markets
+/// between the restatements and the snapshot.
+pub fn handler(ctx: Context<ApplySplitToMarket>) -> Result<()> {
+    let split = &ctx.accounts.split;
+    require!(
+        split.status == SplitStatus::Declared,
+        CorporateActionError::SnapshotAlreadyRecorded
+    );
+    require!(
+        Clock::get()?.unix_timestamp >= split.record_date,
+        CorporateActionError::RecordDateNotReached
+    );
+
+    let clock = Clock::get()?;
+    require!(
+        split.markets_rescaled == 0 || split.snapshot_slot == clock.slot,
+        CorporateActionError::MarketsNotRestatedTogether
+    );
+
+    let minted = split.bonus_for(ctx.accounts.base_vault.amount)?;
+
+    exchange_core::cpi::apply_corporate_action(
+        CpiContext::new(
+            ctx.accounts.exchange_program.to_account_info(),
+            ApplyCorporateAction {
+                exchange: ctx.accounts.exchange.to_account_info(),
+                order_book: ctx.accounts.order_book.to_account_info(),
+                base_mint: ctx.accounts.base_mint.to_account_info(),
+                base_vault: ctx.accounts.base_vault.to_account_info(),
+                bids: ctx.accounts.bids.to_account_info(),
+                asks: ctx.accounts.asks.to_account_info(),
+                event_queue: ctx.accounts.event_queue.to_account_info(),
+                authority: ctx.accounts.authority.to_account_info(),
+                mint_authority: ctx.accounts.issuer.to_account_info(),
+                base_token_program: ctx.accounts.base_token_program.to_account_info(),
+            },
+        ),
+        split.share_multiplier,
+    )?;
+
+    let split_market = &mut ctx.accounts.split_market;
+    split_market.split = ctx.accounts.split.key();
+    split_market.order_book = ctx.accounts.order_book.key();
+    split_market.minted = minted;
+    split_market.applied_at = clock.unix_timestamp;
+    split_market.bump = ctx.bumps.split_market;
+
+    let split = &mut ctx.accounts.split;
+    split.snapshot_slot = clock.slot;
+    split.markets_rescaled = split
+        .markets_rescaled
+        .checked_add(1)
+        .ok_or(CorporateActionError::Overflow)?;
+
+    msg!(
+        "Split {} applied to order book {}: {} shares minted to its base vault",
+        split.split_id,
+        ctx.accounts.order_book.key(),
+        minted
+    );
+
+    Ok(())
+}
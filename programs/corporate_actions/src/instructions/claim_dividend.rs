@@ -0,0 +1,117 @@
+use crate::constants::*;
+use crate::error::CorporateActionError;
+use crate::merkle;
+use crate::state::*;
+use crate::vault::*;
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+#[derive(Accounts)]
+pub struct ClaimDividend<'info> {
+    #[account(
+        mut,
+        has_one = payout_mint,
+        has_one = vault,
+    )]
+    pub dividend: Account<'info, Dividend>,
+
+    /// Created on the first claim, so a holder can never be paid twice
+    #[account(
+        init,
+        payer = holder,
+        space = DividendClaim::LEN,
+        seeds = [DIVIDEND_CLAIM_SEED, dividend.key().as_ref(), holder.key().as_ref()],
+        bump
+    )]
+    pub claim: Account<'info, DividendClaim>,
+
+    pub payout_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(mut)]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        token::mint = payout_mint,
+    )]
+    pub holder_payout_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub holder: Signer<'info>,
+
+    pub payout_token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+/// `shares` and `proof` are the holder's leaf in the record-date snapshot
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, ClaimDividend<'info>>,
+    shares: u64,
+    proof: Vec<[u8; 32]>,
+) -> Result<()> {
+    let dividend = &ctx.accounts.dividend;
+    require!(
+        dividend.status == DividendStatus::Payable,
+        CorporateActionError::NotPayable
+    );
+    let now = Clock::get()?.unix_timestamp;
+    require!(
+        now < dividend.claim_deadline,
+        CorporateActionError::ClaimPeriodEnded
+    );
+    require!(
+        proof.len() <= MAX_PROOF_LENGTH
+            && merkle::verify(
+                &proof,
+                &dividend.snapshot_root,
+                merkle::holding_leaf(&ctx.accounts.holder.key(), shares),
+            ),
+        CorporateActionError::InvalidProof
+    );
+
+    // A snapshot whose leaves add up to more than its total cannot pay out past it
+    let claimed_shares = dividend
+        .claimed_shares
+        .checked_add(shares)
+        .ok_or(CorporateActionError::Overflow)?;
+    require!(
+        claimed_shares <= dividend.total_shares,
+        CorporateActionError::SnapshotOverclaimed
+    );
+
+    let amount = dividend.payout_for(shares)?;
+    release_tokens(
+        dividend,
+        &ctx.accounts.vault,
+        &ctx.accounts.payout_mint,
+        ctx.accounts.holder_payout_account.to_account_info(),
+        &ctx.accounts.payout_token_program,
+        ctx.remaining_accounts,
+        amount,
+    )?;
+
+    let claim = &mut ctx.accounts.claim;
+    claim.dividend = dividend.key();
+    claim.holder = ctx.accounts.holder.key();
+    claim.shares = shares;
+    claim.amount = amount;
+    claim.claimed_at = now;
+    claim.bump = ctx.bumps.claim;
+
+    let dividend = &mut ctx.accounts.dividend;
+    dividend.claimed_shares = claimed_shares;
+    dividend.claimed_amount = dividend
+        .claimed_amount
+        .checked_add(amount)
+        .ok_or(CorporateActionError::Overflow)?;
+
+    msg!(
+        "Dividend {} paid to {}: {} for {} shares",
+        dividend.dividend_id,
+        claim.holder,
+        amount,
+        shares
+    );
+
+    Ok(())
+}
@@ -0,0 +1,108 @@
+use crate::constants::*;
+use crate::error::CorporateActionError;
+use crate::merkle;
+use crate::state::*;
+use crate::vault::*;
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+#[derive(Accounts)]
+pub struct ClaimSplitShares<'info> {
+    #[account(
+        mut,
+        has_one = base_mint,
+        has_one = vault,
+    )]
+    pub split: Account<'info, Split>,
+
+    /// Created on the first claim, so a holder can never be paid twice
+    #[account(
+        init,
+        payer = holder,
+        space = SplitClaim::LEN,
+        seeds = [SPLIT_CLAIM_SEED, split.key().as_ref(), holder.key().as_ref()],
+        bump
+    )]
+    pub claim: Account<'info, SplitClaim>,
+
+    pub base_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(mut)]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        token::mint = base_mint,
+    )]
+    pub holder_base_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub holder: Signer<'info>,
+
+    pub base_token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+/// `shares` and `proof` are the holder's leaf in the split snapshot
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, ClaimSplitShares<'info>>,
+    shares: u64,
+    proof: Vec<[u8; 32]>,
+) -> Result<()> {
+    let split = &ctx.accounts.split;
+    require!(
+        split.status == SplitStatus::Claimable,
+        CorporateActionError::NotClaimable
+    );
+    require!(
+        proof.len() <= MAX_PROOF_LENGTH
+            && merkle::verify(
+                &proof,
+                &split.snapshot_root,
+                merkle::holding_leaf(&ctx.accounts.holder.key(), shares),
+            ),
+        CorporateActionError::InvalidProof
+    );
+
+    // As for dividends, claims stop once they add up to the snapshot's total
+    let claimed_shares = split
+        .claimed_shares
+        .checked_add(shares)
+        .ok_or(CorporateActionError::Overflow)?;
+    require!(
+        claimed_shares <= split.total_shares,
+        CorporateActionError::SnapshotOverclaimed
+    );
+
+    let bonus_shares = split.bonus_for(shares)?;
+    release_shares(
+        split,
+        &ctx.accounts.vault,
+        &ctx.accounts.base_mint,
+        ctx.accounts.holder_base_account.to_account_info(),
+        &ctx.accounts.base_token_program,
+        ctx.remaining_accounts,
+        bonus_shares,
+    )?;
+
+    let claim = &mut ctx.accounts.claim;
+    claim.split = split.key();
+    claim.holder = ctx.accounts.holder.key();
+    claim.shares = shares;
+    claim.bonus_shares = bonus_shares;
+    claim.claimed_at = Clock::get()?.unix_timestamp;
+    claim.bump = ctx.bumps.claim;
+
+    let split = &mut ctx.accounts.split;
+    split.claimed_shares = claimed_shares;
+
+    msg!(
+        "Split {} shares delivered to {}: {} new shares for {}",
+        split.split_id,
+        claim.holder,
+        bonus_shares,
+        shares
+    );
+
+    Ok(())
+}
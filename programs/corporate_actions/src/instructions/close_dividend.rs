@@ -0,0 +1,66 @@
+use crate::error::CorporateActionError;
+use crate::state::*;
+use crate::vault::*;
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+#[derive(Accounts)]
+pub struct CloseDividend<'info> {
+    #[account(
+        mut,
+        has_one = issuer @ CorporateActionError::Unauthorized,
+        has_one = payout_mint,
+        has_one = vault,
+    )]
+    pub dividend: Account<'info, Dividend>,
+
+    pub payout_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(mut)]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        token::mint = payout_mint,
+        token::authority = issuer,
+    )]
+    pub issuer_payout_account: InterfaceAccount<'info, TokenAccount>,
+
+    pub issuer: Signer<'info>,
+
+    pub payout_token_program: Interface<'info, TokenInterface>,
+}
+
+/// Return whatever was not claimed by the deadline to the issuer
+pub fn handler<'info>(ctx: Context<'_, '_, 'info, 'info, CloseDividend<'info>>) -> Result<()> {
+    let dividend = &ctx.accounts.dividend;
+    require!(
+        dividend.status != DividendStatus::Closed,
+        CorporateActionError::NotPayable
+    );
+    require!(
+        Clock::get()?.unix_timestamp >= dividend.claim_deadline,
+        CorporateActionError::ClaimPeriodNotEnded
+    );
+
+    let unclaimed = ctx.accounts.vault.amount;
+    release_tokens(
+        dividend,
+        &ctx.accounts.vault,
+        &ctx.accounts.payout_mint,
+        ctx.accounts.issuer_payout_account.to_account_info(),
+        &ctx.accounts.payout_token_program,
+        ctx.remaining_accounts,
+        unclaimed,
+    )?;
+
+    ctx.accounts.dividend.status = DividendStatus::Closed;
+
+    msg!(
+        "Dividend {} closed, {} unclaimed returned",
+        ctx.accounts.dividend.dividend_id,
+        unclaimed
+    );
+
+    Ok(())
+}
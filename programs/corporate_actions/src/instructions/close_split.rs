@@ -0,0 +1,51 @@
+use crate::error::CorporateActionError;
+use crate::state::*;
+use crate::vault::*;
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{TokenAccount, TokenInterface};
+
+#[derive(Accounts)]
+pub struct CloseSplit<'info> {
+    #[account(
+        mut,
+        has_one = issuer @ CorporateActionError::Unauthorized,
+        has_one = vault,
+    )]
+    pub split: Account<'info, Split>,
+
+    #[account(mut)]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+
+    /// Receives the vault's rent
+    #[account(mut)]
+    pub issuer: Signer<'info>,
+
+    pub base_token_program: Interface<'info, TokenInterface>,
+}
+
+/// Close the vault once every share in the snapshot has been claimed for,
+/// which leaves it empty
+pub fn handler(ctx: Context<CloseSplit>) -> Result<()> {
+    let split = &ctx.accounts.split;
+    require!(
+        split.status == SplitStatus::Claimable,
+        CorporateActionError::NotClaimable
+    );
+    require!(
+        split.claimed_shares == split.total_shares,
+        CorporateActionError::SharesUnclaimed
+    );
+
+    close_split_vault(
+        split,
+        &ctx.accounts.vault,
+        ctx.accounts.issuer.to_account_info(),
+        &ctx.accounts.base_token_program,
+    )?;
+
+    ctx.accounts.split.status = SplitStatus::Closed;
+
+    msg!("Split {} closed", ctx.accounts.split.split_id);
+
+    Ok(())
+}
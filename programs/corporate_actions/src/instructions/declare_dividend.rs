@@ -0,0 +1,84 @@
+use crate::constants::*;
+use crate::error::CorporateActionError;
+use crate::state::*;
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program_option::COption;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+#[derive(Accounts)]
+#[instruction(dividend_id: u64)]
+pub struct DeclareDividend<'info> {
+    #[account(
+        init,
+        payer = issuer,
+        space = Dividend::LEN,
+        seeds = [DIVIDEND_SEED, base_mint.key().as_ref(), dividend_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub dividend: Account<'info, Dividend>,
+
+    #[account(
+        constraint = base_mint.mint_authority == COption::Some(issuer.key()) @ CorporateActionError::Unauthorized,
+    )]
+    pub base_mint: InterfaceAccount<'info, Mint>,
+
+    pub payout_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        init,
+        payer = issuer,
+        token::mint = payout_mint,
+        token::authority = dividend,
+        token::token_program = payout_token_program,
+        seeds = [VAULT_SEED, dividend.key().as_ref()],
+        bump
+    )]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub issuer: Signer<'info>,
+
+    pub payout_token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(
+    ctx: Context<DeclareDividend>,
+    dividend_id: u64,
+    amount_per_share: u64,
+    record_date: i64,
+    claim_deadline: i64,
+) -> Result<()> {
+    require!(
+        record_date > Clock::get()?.unix_timestamp && record_date < claim_deadline,
+        CorporateActionError::InvalidRecordDate
+    );
+
+    let dividend = &mut ctx.accounts.dividend;
+    dividend.issuer = ctx.accounts.issuer.key();
+    dividend.base_mint = ctx.accounts.base_mint.key();
+    dividend.base_decimals = ctx.accounts.base_mint.decimals;
+    dividend.dividend_id = dividend_id;
+    dividend.payout_mint = ctx.accounts.payout_mint.key();
+    dividend.vault = ctx.accounts.vault.key();
+    dividend.amount_per_share = amount_per_share;
+    dividend.record_date = record_date;
+    dividend.claim_deadline = claim_deadline;
+    dividend.snapshot_root = [0; 32];
+    dividend.total_shares = 0;
+    dividend.record_supply = 0;
+    dividend.claimed_shares = 0;
+    dividend.claimed_amount = 0;
+    dividend.status = DividendStatus::Declared;
+    dividend.bump = ctx.bumps.dividend;
+
+    msg!(
+        "Dividend {} declared on {}: {} per share, record date {}",
+        dividend_id,
+        dividend.base_mint,
+        amount_per_share,
+        record_date
+    );
+
+    Ok(())
+}
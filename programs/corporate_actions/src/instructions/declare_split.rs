@@ -0,0 +1,83 @@
+use crate::constants::*;
+use crate::error::CorporateActionError;
+use crate::state::*;
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program_option::COption;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+#[derive(Accounts)]
+#[instruction(split_id: u64)]
+pub struct DeclareSplit<'info> {
+    #[account(
+        init,
+        payer = issuer,
+        space = Split::LEN,
+        seeds = [SPLIT_SEED, base_mint.key().as_ref(), split_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub split: Account<'info, Split>,
+
+    #[account(
+        constraint = base_mint.mint_authority == COption::Some(issuer.key()) @ CorporateActionError::Unauthorized,
+    )]
+    pub base_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        init,
+        payer = issuer,
+        token::mint = base_mint,
+        token::authority = split,
+        token::token_program = base_token_program,
+        seeds = [VAULT_SEED, split.key().as_ref()],
+        bump
+    )]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub issuer: Signer<'info>,
+
+    pub base_token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(
+    ctx: Context<DeclareSplit>,
+    split_id: u64,
+    share_multiplier: u64,
+    record_date: i64,
+) -> Result<()> {
+    require!(
+        share_multiplier >= 2,
+        CorporateActionError::InvalidShareMultiplier
+    );
+    require!(
+        record_date > Clock::get()?.unix_timestamp,
+        CorporateActionError::InvalidRecordDate
+    );
+
+    let split = &mut ctx.accounts.split;
+    split.issuer = ctx.accounts.issuer.key();
+    split.base_mint = ctx.accounts.base_mint.key();
+    split.split_id = split_id;
+    split.share_multiplier = share_multiplier;
+    split.vault = ctx.accounts.vault.key();
+    split.record_date = record_date;
+    split.snapshot_slot = 0;
+    split.snapshot_root = [0; 32];
+    split.total_shares = 0;
+    split.bonus_shares = 0;
+    split.claimed_shares = 0;
+    split.markets_rescaled = 0;
+    split.status = SplitStatus::Declared;
+    split.bump = ctx.bumps.split;
+
+    msg!(
+        "Split {} declared on {}: {} shares per share, record date {}",
+        split_id,
+        split.base_mint,
+        share_multiplier,
+        record_date
+    );
+
+    Ok(())
+}
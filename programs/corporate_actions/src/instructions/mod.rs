@@ -0,0 +1,43 @@
+pub mod apply_split_to_market;
+pub mod claim_dividend;
+pub mod claim_split_shares;
+pub mod close_dividend;
+pub mod close_split;
+pub mod declare_dividend;
+pub mod declare_split;
+pub mod record_snapshot;
+pub mod record_split_snapshot;
+
+pub use apply_split_to_market::ApplySplitToMarket;
+pub use claim_dividend::ClaimDividend;
+pub use claim_split_shares::ClaimSplitShares;
+pub use close_dividend::CloseDividend;
+pub use close_split::CloseSplit;
+pub use declare_dividend::DeclareDividend;
+pub use declare_split::DeclareSplit;
+pub use record_snapshot::RecordSnapshot;
+pub use record_split_snapshot::RecordSplitSnapshot;
+
+// Only the accounts structs are re-exported, as every module has a `handler`;
+// `#[program]` also looks for the client modules their derive generates here
+pub(crate) use apply_split_to_market::__client_accounts_apply_split_to_market;
+pub(crate) use claim_dividend::__client_accounts_claim_dividend;
+pub(crate) use claim_split_shares::__client_accounts_claim_split_shares;
+pub(crate) use close_dividend::__client_accounts_close_dividend;
+pub(crate) use close_split::__client_accounts_close_split;
+pub(crate) use declare_dividend::__client_accounts_declare_dividend;
+pub(crate) use declare_split::__client_accounts_declare_split;
+pub(crate) use record_snapshot::__client_accounts_record_snapshot;
+pub(crate) use record_split_snapshot::__client_accounts_record_split_snapshot;
+#[cfg(feature = "cpi")]
+pub(crate) use {
+    apply_split_to_market::__cpi_client_accounts_apply_split_to_market,
+    claim_dividend::__cpi_client_accounts_claim_dividend,
+    claim_split_shares::__cpi_client_accounts_claim_split_shares,
+    close_dividend::__cpi_client_accounts_close_dividend,
+    close_split::__cpi_client_accounts_close_split,
+    declare_dividend::__cpi_client_accounts_declare_dividend,
+    declare_split::__cpi_client_accounts_declare_split,
+    record_snapshot::__cpi_client_accounts_record_snapshot,
+    record_split_snapshot::__cpi_client_accounts_record_split_snapshot,
+};
@@ -0,0 +1,88 @@
+use crate::error::CorporateActionError;
+use crate::state::*;
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
+
+#[derive(Accounts)]
+pub struct RecordSnapshot<'info> {
+    #[account(
+        mut,
+        has_one = issuer @ CorporateActionError::Unauthorized,
+        has_one = base_mint,
+        has_one = payout_mint,
+        has_one = vault,
+    )]
+    pub dividend: Account<'info, Dividend>,
+
+    pub base_mint: InterfaceAccount<'info, Mint>,
+
+    pub payout_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(mut)]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        token::mint = payout_mint,
+        token::authority = issuer,
+    )]
+    pub issuer_payout_account: InterfaceAccount<'info, TokenAccount>,
+
+    pub issuer: Signer<'info>,
+
+    pub payout_token_program: Interface<'info, TokenInterface>,
+}
+
+/// Freeze the holders of record and fund the vault with the full payout.
+/// The snapshot can only be recorded once, after the record date.
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, RecordSnapshot<'info>>,
+    snapshot_root: [u8; 32],
+    total_shares: u64,
+) -> Result<()> {
+    let dividend = &mut ctx.accounts.dividend;
+    require!(
+        dividend.status == DividendStatus::Declared,
+        CorporateActionError::SnapshotAlreadyRecorded
+    );
+    require!(
+        Clock::get()?.unix_timestamp >= dividend.record_date,
+        CorporateActionError::RecordDateNotReached
+    );
+    let record_supply = ctx.accounts.base_mint.supply;
+    require!(
+        total_shares > 0 && total_shares <= record_supply,
+        CorporateActionError::InvalidSnapshot
+    );
+
+    dividend.snapshot_root = snapshot_root;
+    dividend.total_shares = total_shares;
+    dividend.record_supply = record_supply;
+    dividend.status = DividendStatus::Payable;
+
+    // Every holder's payout rounds down, so this always covers all claims
+    let funding = dividend.payout_for(total_shares)?;
+    token_interface::transfer_checked(
+        CpiContext::new(
+            ctx.accounts.payout_token_program.to_account_info(),
+            TransferChecked {
+                from: ctx.accounts.issuer_payout_account.to_account_info(),
+                mint: ctx.accounts.payout_mint.to_account_info(),
+                to: ctx.accounts.vault.to_account_info(),
+                authority: ctx.accounts.issuer.to_account_info(),
+            },
+        )
+        .with_remaining_accounts(ctx.remaining_accounts.to_vec()),
+        funding,
+        ctx.accounts.payout_mint.decimals,
+    )?;
+
+    msg!(
+        "Dividend {} snapshot recorded: {} shares, {} funded",
+        dividend.dividend_id,
+        total_shares,
+        funding
+    );
+
+    Ok(())
+}
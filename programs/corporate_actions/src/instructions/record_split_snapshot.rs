@@ -0,0 +1,75 @@
+use crate::error::CorporateActionError;
+use crate::state::*;
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, Mint, MintTo, TokenAccount, TokenInterface};
+
+#[derive(Accounts)]
+pub struct RecordSplitSnapshot<'info> {
+    #[account(
+        mut,
+        has_one = issuer @ CorporateActionError::Unauthorized,
+        has_one = base_mint,
+        has_one = vault,
+    )]
+    pub split: Account<'info, Split>,
+
+    #[account(mut)]
+    pub base_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(mut)]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+
+    pub issuer: Signer<'info>,
+
+    pub base_token_program: Interface<'info, TokenInterface>,
+}
+
+/// Freeze the holders outside the exchange and mint their new shares into the
+/// vault. Markets must be restated first, since the snapshot is taken at the
+/// slot they were; without any, it is taken at the record date.
+pub fn handler(
+    ctx: Context<RecordSplitSnapshot>,
+    snapshot_root: [u8; 32],
+    total_shares: u64,
+) -> Result<()> {
+    let split = &mut ctx.accounts.split;
+    require!(
+        split.status == SplitStatus::Declared,
+        CorporateActionError::SnapshotAlreadyRecorded
+    );
+    require!(
+        Clock::get()?.unix_timestamp >= split.record_date,
+        CorporateActionError::RecordDateNotReached
+    );
+    require!(
+        total_shares > 0 && total_shares <= ctx.accounts.base_mint.supply,
+        CorporateActionError::InvalidSnapshot
+    );
+
+    let bonus_shares = split.bonus_for(total_shares)?;
+    split.snapshot_root = snapshot_root;
+    split.total_shares = total_shares;
+    split.bonus_shares = bonus_shares;
+    split.status = SplitStatus::Claimable;
+
+    token_interface::mint_to(
+        CpiContext::new(
+            ctx.accounts.base_token_program.to_account_info(),
+            MintTo {
+                mint: ctx.accounts.base_mint.to_account_info(),
+                to: ctx.accounts.vault.to_account_info(),
+                authority: ctx.accounts.issuer.to_account_info(),
+            },
+        ),
+        bonus_shares,
+    )?;
+
+    msg!(
+        "Split {} snapshot recorded: {} shares, {} new shares minted",
+        split.split_id,
+        total_shares,
+        bonus_shares
+    );
+
+    Ok(())
+}
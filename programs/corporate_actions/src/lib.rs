@@ -0,0 +1,121 @@
+//! Dividends and share splits paid out against record-date snapshots.
+//!
+//! # Trust model
+//!
+//! Holdings at the record date cannot be read on chain, so each snapshot is a
+//! Merkle root over `(holder, shares)` leaves that the issuer builds off-chain
+//! and attests to by signing as the mint authority. Markets' base vaults are
+//! always left out. In a dividend snapshot, shares held through an
+//! exchange_core market count for their owner: each trader's
+//! `base_free + base_locked` on the market's open orders account is added to
+//! their holding. A split snapshot leaves those holdings out, as
+//! `apply_split_to_market` restates them in place.
+//!
+//! The program does not check the individual leaves, only bounds the damage a
+//! wrong root can do: the snapshot's `total_shares` may not exceed the mint
+//! supply recorded with it, and the vault is funded for exactly
+//! `total_shares`, so claims stop once they add up to it. A root that leaves
+//! holders out or overstates some of them can only shortchange other holders
+//! of the same action, never pay out more than the issuer put in.
+
+pub mod constants;
+pub mod error;
+pub mod instructions;
+pub mod merkle;
+pub mod state;
+pub mod vault;
+
+use anchor_lang::prelude::*;
+use instructions::*;
+
+declare_id!("CaxpxzkEWKKKBcuhcLuHTodoJ9oxFjV83KNUdzosq9vE");
+
+#[program]
+pub mod corporate_actions {
+    use super::*;
+
+    /// Declare a cash dividend on a share mint (mint authority only)
+    pub fn declare_dividend(
+        ctx: Context<DeclareDividend>,
+        dividend_id: u64,
+        amount_per_share: u64,
+        record_date: i64,
+        claim_deadline: i64,
+    ) -> Result<()> {
+        instructions::declare_dividend::handler(
+            ctx,
+            dividend_id,
+            amount_per_share,
+            record_date,
+            claim_deadline,
+        )
+    }
+
+    /// Record the holders of record as a Merkle root after the record date
+    /// and fund the dividend vault (mint authority only)
+    pub fn record_snapshot<'info>(
+        ctx: Context<'_, '_, 'info, 'info, RecordSnapshot<'info>>,
+        snapshot_root: [u8; 32],
+        total_shares: u64,
+    ) -> Result<()> {
+        instructions::record_snapshot::handler(ctx, snapshot_root, total_shares)
+    }
+
+    /// Claim `amount_per_share * shares` by proving the holding in the snapshot
+    pub fn claim_dividend<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ClaimDividend<'info>>,
+        shares: u64,
+        proof: Vec<[u8; 32]>,
+    ) -> Result<()> {
+        instructions::claim_dividend::handler(ctx, shares, proof)
+    }
+
+    /// Return unclaimed funds to the issuer after the claim deadline
+    pub fn close_dividend<'info>(
+        ctx: Context<'_, '_, 'info, 'info, CloseDividend<'info>>,
+    ) -> Result<()> {
+        instructions::close_dividend::handler(ctx)
+    }
+
+    /// Declare a stock split or bonus issue giving `share_multiplier` shares
+    /// per share held at the record date (mint authority only)
+    pub fn declare_split(
+        ctx: Context<DeclareSplit>,
+        split_id: u64,
+        share_multiplier: u64,
+        record_date: i64,
+    ) -> Result<()> {
+        instructions::declare_split::handler(ctx, split_id, share_multiplier, record_date)
+    }
+
+    /// Mint the new shares into a market's base vault and restate its orders,
+    /// halting it until its accounts are rescaled (mint and exchange authority)
+    pub fn apply_split_to_market(ctx: Context<ApplySplitToMarket>) -> Result<()> {
+        instructions::apply_split_to_market::handler(ctx)
+    }
+
+    /// Record the holders outside the exchange as a Merkle root and mint their
+    /// new shares into the split vault (mint authority only)
+    pub fn record_split_snapshot(
+        ctx: Context<RecordSplitSnapshot>,
+        snapshot_root: [u8; 32],
+        total_shares: u64,
+    ) -> Result<()> {
+        instructions::record_split_snapshot::handler(ctx, snapshot_root, total_shares)
+    }
+
+    /// Claim the new shares for `shares` held by proving the holding in the snapshot
+    pub fn claim_split_shares<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ClaimSplitShares<'info>>,
+        shares: u64,
+        proof: Vec<[u8; 32]>,
+    ) -> Result<()> {
+        instructions::claim_split_shares::handler(ctx, shares, proof)
+    }
+
+    /// Close the split vault and reclaim its rent once every share has been
+    /// claimed for (mint authority only)
+    pub fn close_split(ctx: Context<CloseSplit>) -> Result<()> {
+        instructions::close_split::handler(ctx)
+    }
+}
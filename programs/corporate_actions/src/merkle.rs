@@ -0,0 +1,84 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hashv;
+
+// Domain separation so an inner node can never be passed off as a leaf
+const LEAF_PREFIX: &[u8] = &[0];
+const NODE_PREFIX: &[u8] = &[1];
+
+/// Leaf for one holder in a record-date snapshot
+pub fn holding_leaf(holder: &Pubkey, shares: u64) -> [u8; 32] {
+    hashv(&[LEAF_PREFIX, holder.as_ref(), &shares.to_le_bytes()]).to_bytes()
+}
+
+/// Check `leaf` against `root`; sibling pairs are hashed in sorted order
+pub fn verify(proof: &[[u8; 32]], root: &[u8; 32], leaf: [u8; 32]) -> bool {
+    let computed = proof.iter().fold(leaf, |node, sibling| {
+        let (left, right) = if node <= *sibling {
+            (node, *sibling)
+        } else {
+            (*sibling, node)
+        };
+        hashv(&[NODE_PREFIX, &left, &right]).to_bytes()
+    });
+    computed == *root
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parent(a: [u8; 32], b: [u8; 32]) -> [u8; 32] {
+        let (left, right) = if a <= b { (a, b) } else { (b, a) };
+        hashv(&[NODE_PREFIX, &left, &right]).to_bytes()
+    }
+
+    /// Holdings, their leaves and the root over them
+    type Snapshot = (Vec<(Pubkey, u64)>, Vec<[u8; 32]>, [u8; 32]);
+
+    /// Four holders and the root over their leaves
+    fn snapshot() -> Snapshot {
+        let holdings: Vec<(Pubkey, u64)> = (1..=4)
+            .map(|shares| (Pubkey::new_unique(), shares))
+            .collect();
+        let leaves: Vec<[u8; 32]> = holdings
+            .iter()
+            .map(|(holder, shares)| holding_leaf(holder, *shares))
+            .collect();
+        let root = parent(parent(leaves[0], leaves[1]), parent(leaves[2], leaves[3]));
+        (holdings, leaves, root)
+    }
+
+    #[test]
+    fn every_holding_in_the_snapshot_verifies() {
+        let (holdings, leaves, root) = snapshot();
+        let proofs = [
+            [leaves[1], parent(leaves[2], leaves[3])],
+            [leaves[0], parent(leaves[2], leaves[3])],
+            [leaves[3], parent(leaves[0], leaves[1])],
+            [leaves[2], parent(leaves[0], leaves[1])],
+        ];
+
+        for ((holder, shares), proof) in holdings.iter().zip(proofs) {
+            assert!(verify(&proof, &root, holding_leaf(holder, *shares)));
+        }
+    }
+
+    #[test]
+    fn a_claim_for_more_shares_or_another_holder_fails() {
+        let (holdings, leaves, root) = snapshot();
+        let proof = [leaves[1], parent(leaves[2], leaves[3])];
+        let (holder, shares) = holdings[0];
+
+        assert!(!verify(&proof, &root, holding_leaf(&holder, shares + 1)));
+        assert!(!verify(&proof, &root, holding_leaf(&holdings[1].0, shares)));
+    }
+
+    #[test]
+    fn a_single_holder_snapshot_needs_no_proof() {
+        let holder = Pubkey::new_unique();
+        let root = holding_leaf(&holder, 7);
+
+        assert!(verify(&[], &root, holding_leaf(&holder, 7)));
+        assert!(!verify(&[], &root, holding_leaf(&holder, 8)));
+    }
+}
@@ -0,0 +1,225 @@
+use crate::error::CorporateActionError;
+use anchor_lang::prelude::*;
+
+/// A cash dividend on `base_mint`, paid to holders of record at `record_date`
+/// against the issuer's Merkle snapshot (see the crate docs for its trust model).
+#[account]
+pub struct Dividend {
+    pub issuer: Pubkey, // mint authority of the shares
+    pub base_mint: Pubkey,
+    pub base_decimals: u8,
+    pub dividend_id: u64, // chosen by the issuer, unique per mint
+    pub payout_mint: Pubkey,
+    pub vault: Pubkey,
+    pub amount_per_share: u64, // payout tokens per whole share
+    pub record_date: i64,
+    pub claim_deadline: i64, // unclaimed funds go back to the issuer after this
+    pub snapshot_root: [u8; 32],
+    pub total_shares: u64,  // in base token units, as in the snapshot leaves
+    pub record_supply: u64, // base_mint supply when the snapshot was recorded
+    pub claimed_shares: u64,
+    pub claimed_amount: u64,
+    pub status: DividendStatus,
+    pub bump: u8,
+}
+
+impl Dividend {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // issuer
+        32 + // base_mint
+        1 + // base_decimals
+        8 + // dividend_id
+        32 + // payout_mint
+        32 + // vault
+        8 + // amount_per_share
+        8 + // record_date
+        8 + // claim_deadline
+        32 + // snapshot_root
+        8 + // total_shares
+        8 + // record_supply
+        8 + // claimed_shares
+        8 + // claimed_amount
+        1 + // status
+        1; // bump
+
+    /// Payout owed for `shares` base token units, rounded down
+    pub fn payout_for(&self, shares: u64) -> Result<u64> {
+        let payout = (shares as u128)
+            .checked_mul(self.amount_per_share as u128)
+            .ok_or(CorporateActionError::Overflow)?
+            / 10u128.pow(self.base_decimals as u32);
+        Ok(u64::try_from(payout).map_err(|_| CorporateActionError::Overflow)?)
+    }
+}
+
+/// Marks a holder's dividend as paid
+#[account]
+pub struct DividendClaim {
+    pub dividend: Pubkey,
+    pub holder: Pubkey,
+    pub shares: u64,
+    pub amount: u64,
+    pub claimed_at: i64,
+    pub bump: u8,
+}
+
+impl DividendClaim {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // dividend
+        32 + // holder
+        8 + // shares
+        8 + // amount
+        8 + // claimed_at
+        1; // bump
+}
+
+/// A stock split or bonus issue on `base_mint` giving `share_multiplier`
+/// shares for every share held (a 1:5 split is 5, a 1:1 bonus is 2).
+///
+/// Shares on exchange_core markets are restated in place: `apply_split_to_market`
+/// mints the extra shares into the market's base vault and rescales its orders
+/// and balances. Everything else is claimed from the split vault against a
+/// Merkle snapshot of wallet balances at `snapshot_slot`, leaving out the
+/// markets' base vaults.
+#[account]
+pub struct Split {
+    pub issuer: Pubkey, // mint authority of the shares
+    pub base_mint: Pubkey,
+    pub split_id: u64, // chosen by the issuer, unique per mint
+    pub share_multiplier: u64,
+    pub vault: Pubkey, // new shares awaiting claims
+    pub record_date: i64,
+    pub snapshot_slot: u64, // slot the markets were restated, 0 if none were
+    pub snapshot_root: [u8; 32],
+    pub total_shares: u64,   // pre-split shares in the snapshot
+    pub bonus_shares: u64,   // minted to the vault for the snapshot
+    pub claimed_shares: u64, // pre-split shares claimed for, as for dividends
+    pub markets_rescaled: u32,
+    pub status: SplitStatus,
+    pub bump: u8,
+}
+
+impl Split {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // issuer
+        32 + // base_mint
+        8 + // split_id
+        8 + // share_multiplier
+        32 + // vault
+        8 + // record_date
+        8 + // snapshot_slot
+        32 + // snapshot_root
+        8 + // total_shares
+        8 + // bonus_shares
+        8 + // claimed_shares
+        4 + // markets_rescaled
+        1 + // status
+        1; // bump
+
+    /// New shares owed for `shares` held before the split
+    pub fn bonus_for(&self, shares: u64) -> Result<u64> {
+        shares
+            .checked_mul(self.share_multiplier - 1)
+            .ok_or(CorporateActionError::Overflow.into())
+    }
+}
+
+/// Marks a holder's split shares as delivered
+#[account]
+pub struct SplitClaim {
+    pub split: Pubkey,
+    pub holder: Pubkey,
+    pub shares: u64,
+    pub bonus_shares: u64,
+    pub claimed_at: i64,
+    pub bump: u8,
+}
+
+impl SplitClaim {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // split
+        32 + // holder
+        8 + // shares
+        8 + // bonus_shares
+        8 + // claimed_at
+        1; // bump
+}
+
+/// Marks a split as applied to one exchange market
+#[account]
+pub struct SplitMarket {
+    pub split: Pubkey,
+    pub order_book: Pubkey,
+    pub minted: u64, // new shares minted into the market's base vault
+    pub applied_at: i64,
+    pub bump: u8,
+}
+
+impl SplitMarket {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // split
+        32 + // order_book
+        8 + // minted
+        8 + // applied_at
+        1; // bump
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum DividendStatus {
+    Declared, // waiting for the record date snapshot
+    Payable,  // snapshot recorded and vault funded
+    Closed,   // claim period over, unclaimed funds returned
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum SplitStatus {
+    Declared,  // markets can be restated until the snapshot is recorded
+    Claimable, // snapshot recorded and vault funded
+    Closed,    // every share claimed and the vault closed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// An account with every field zeroed, `len` including the discriminator
+    fn blank<T: AnchorDeserialize>(len: usize) -> T {
+        T::deserialize(&mut &vec![0; len - 8][..]).unwrap()
+    }
+
+    fn dividend(amount_per_share: u64, base_decimals: u8) -> Dividend {
+        Dividend {
+            amount_per_share,
+            base_decimals,
+            ..blank(Dividend::LEN)
+        }
+    }
+
+    #[test]
+    fn payouts_are_per_whole_share_and_round_down() {
+        // 0.25 payout tokens per share of a 2-decimal token
+        let quarter = dividend(250_000, 2);
+
+        assert_eq!(quarter.payout_for(100).unwrap(), 250_000);
+        assert_eq!(quarter.payout_for(150).unwrap(), 375_000);
+        assert_eq!(quarter.payout_for(1).unwrap(), 2_500);
+        assert_eq!(dividend(3, 2).payout_for(33).unwrap(), 0);
+    }
+
+    #[test]
+    fn payouts_overflowing_u64_fail() {
+        assert!(dividend(u64::MAX, 0).payout_for(2).is_err());
+    }
+
+    #[test]
+    fn split_bonus_is_the_new_shares_on_top_of_each_held_share() {
+        let three_for_one = Split {
+            share_multiplier: 3,
+            ..blank(Split::LEN)
+        };
+
+        assert_eq!(three_for_one.bonus_for(100).unwrap(), 200);
+        assert_eq!(three_for_one.bonus_for(0).unwrap(), 0);
+        assert!(three_for_one.bonus_for(u64::MAX).is_err());
+    }
+}
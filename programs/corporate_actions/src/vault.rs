@@ -0,0 +1,111 @@
+use crate::constants::*;
+use crate::state::*;
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{
+    self, CloseAccount, Mint, TokenAccount, TokenInterface, TransferChecked,
+};
+
+/// Transfer payout tokens out of a dividend vault, signed by the dividend PDA.
+/// `extra_accounts` are forwarded so Token-2022 transfer hooks can resolve theirs.
+pub fn release_tokens<'info>(
+    dividend: &Account<'info, Dividend>,
+    vault: &InterfaceAccount<'info, TokenAccount>,
+    mint: &InterfaceAccount<'info, Mint>,
+    to: AccountInfo<'info>,
+    token_program: &Interface<'info, TokenInterface>,
+    extra_accounts: &[AccountInfo<'info>],
+    amount: u64,
+) -> Result<()> {
+    if amount == 0 {
+        return Ok(());
+    }
+
+    let dividend_id = dividend.dividend_id.to_le_bytes();
+    let signer_seeds: &[&[u8]] = &[
+        DIVIDEND_SEED,
+        dividend.base_mint.as_ref(),
+        dividend_id.as_ref(),
+        &[dividend.bump],
+    ];
+
+    token_interface::transfer_checked(
+        CpiContext::new_with_signer(
+            token_program.to_account_info(),
+            TransferChecked {
+                from: vault.to_account_info(),
+                mint: mint.to_account_info(),
+                to,
+                authority: dividend.to_account_info(),
+            },
+            &[signer_seeds],
+        )
+        .with_remaining_accounts(extra_accounts.to_vec()),
+        amount,
+        mint.decimals,
+    )
+}
+
+/// Transfer new shares out of a split vault, signed by the split PDA
+pub fn release_shares<'info>(
+    split: &Account<'info, Split>,
+    vault: &InterfaceAccount<'info, TokenAccount>,
+    mint: &InterfaceAccount<'info, Mint>,
+    to: AccountInfo<'info>,
+    token_program: &Interface<'info, TokenInterface>,
+    extra_accounts: &[AccountInfo<'info>],
+    amount: u64,
+) -> Result<()> {
+    if amount == 0 {
+        return Ok(());
+    }
+
+    let split_id = split.split_id.to_le_bytes();
+    let signer_seeds: &[&[u8]] = &[
+        SPLIT_SEED,
+        split.base_mint.as_ref(),
+        split_id.as_ref(),
+        &[split.bump],
+    ];
+
+    token_interface::transfer_checked(
+        CpiContext::new_with_signer(
+            token_program.to_account_info(),
+            TransferChecked {
+                from: vault.to_account_info(),
+                mint: mint.to_account_info(),
+                to,
+                authority: split.to_account_info(),
+            },
+            &[signer_seeds],
+        )
+        .with_remaining_accounts(extra_accounts.to_vec()),
+        amount,
+        mint.decimals,
+    )
+}
+
+/// Close an emptied split vault, returning its rent to `to`
+pub fn close_split_vault<'info>(
+    split: &Account<'info, Split>,
+    vault: &InterfaceAccount<'info, TokenAccount>,
+    to: AccountInfo<'info>,
+    token_program: &Interface<'info, TokenInterface>,
+) -> Result<()> {
+    let split_id = split.split_id.to_le_bytes();
+    let signer_seeds: &[&[u8]] = &[
+        SPLIT_SEED,
+        split.base_mint.as_ref(),
+        split_id.as_ref(),
+        &[split.bump],
+    ];
+
+    token_interface::close_account(CpiContext::new_with_signer(
+        token_program.to_account_info(),
+        CloseAccount {
+            account: vault.to_account_info(),
+            destination: to,
+            authority: split.to_account_info(),
+        },
+        &[signer_seeds],
+    ))
+}
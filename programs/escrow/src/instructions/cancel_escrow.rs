@@ -1,5 +1,5 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
 use crate::constants::*;
 use crate::error::EscrowError;
 use crate::state::*;
@@ -20,8 +20,14 @@ pub struct CancelEscrow<'info> {
     )]
     pub canceller: Signer<'info>,
     
-    #[account(mut)]
-    pub base_vault: Account<'info, TokenAccount>,
+    #[account(
+        mut,
+        constraint = base_vault.key() == escrow.base_vault
+    )]
+    pub base_vault: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(address = escrow.base_mint)]
+    pub base_mint: InterfaceAccount<'info, Mint>,
     
     /// CHECK: SOL vault PDA
     #[account(mut)]
@@ -29,17 +35,17 @@ pub struct CancelEscrow<'info> {
     
     /// CHECK: Seller receives base token refund
     #[account(mut)]
-    pub seller_refund_account: Account<'info, TokenAccount>,
+    pub seller_refund_account: InterfaceAccount<'info, TokenAccount>,
     
     /// CHECK: Buyer receives SOL refund
     #[account(mut)]
     pub buyer: AccountInfo<'info>,
     
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
     pub system_program: Program<'info, System>,
 }
 
-pub fn handler(ctx: Context<CancelEscrow>) -> Result<()> {
+pub fn handler<'info>(ctx: Context<'_, '_, 'info, 'info, CancelEscrow<'info>>) -> Result<()> {
     let escrow = &mut ctx.accounts.escrow;
     let current_time = Clock::get()?.unix_timestamp;
     
@@ -58,18 +64,21 @@ pub fn handler(ctx: Context<CancelEscrow>) -> Result<()> {
 
     // Refund base tokens if any deposited
     if escrow.base_deposited > 0 {
-        let refund_base = Transfer {
+        let refund_base = TransferChecked {
             from: ctx.accounts.base_vault.to_account_info(),
+            mint: ctx.accounts.base_mint.to_account_info(),
             to: ctx.accounts.seller_refund_account.to_account_info(),
             authority: escrow.to_account_info(),
         };
-        token::transfer(
+        token_interface::transfer_checked(
             CpiContext::new_with_signer(
                 ctx.accounts.token_program.to_account_info(),
                 refund_base,
                 &[signer_seeds]
-            ),
-            escrow.base_deposited
+            )
+            .with_remaining_accounts(ctx.remaining_accounts.to_vec()),
+            escrow.base_deposited,
+            ctx.accounts.base_mint.decimals
         )?;
     }
 
@@ -1,5 +1,5 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+use anchor_spl::token_interface::{TokenAccount, TokenInterface};
 use crate::constants::*;
 use crate::error::EscrowError;
 use crate::state::*;
@@ -17,15 +17,15 @@ pub struct ClaimFromEscrow<'info> {
     pub claimer: Signer<'info>,
     
     #[account(mut)]
-    pub vault: Account<'info, TokenAccount>,
+    pub vault: InterfaceAccount<'info, TokenAccount>,
     
     #[account(
         mut,
         constraint = claimer_token_account.owner == claimer.key()
     )]
-    pub claimer_token_account: Account<'info, TokenAccount>,
+    pub claimer_token_account: InterfaceAccount<'info, TokenAccount>,
     
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
 }
 
 pub fn handler(ctx: Context<ClaimFromEscrow>) -> Result<()> {
@@ -1,6 +1,6 @@
 use anchor_lang::prelude::*;
 use anchor_lang::system_program;
-use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
 use crate::error::EscrowError;
 use crate::state::*;
 
@@ -20,10 +20,16 @@ pub struct DepositToEscrow<'info> {
         mut,
         constraint = depositor_base_account.owner == depositor.key()
     )]
-    pub depositor_base_account: Account<'info, TokenAccount>,
+    pub depositor_base_account: InterfaceAccount<'info, TokenAccount>,
     
-    #[account(mut)]
-    pub base_vault: Account<'info, TokenAccount>,
+    #[account(
+        mut,
+        constraint = base_vault.key() == escrow.base_vault
+    )]
+    pub base_vault: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(address = escrow.base_mint)]
+    pub base_mint: InterfaceAccount<'info, Mint>,
 
     /// CHECK: SOL vault PDA for holding SOL
     #[account(
@@ -33,14 +39,19 @@ pub struct DepositToEscrow<'info> {
     )]
     pub sol_vault: AccountInfo<'info>,
     
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
     pub system_program: Program<'info, System>,
 }
 
-pub fn handler(ctx: Context<DepositToEscrow>, amount: u64, is_base: bool) -> Result<()> {
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, DepositToEscrow<'info>>,
+    amount: u64,
+    is_base: bool,
+) -> Result<()> {
     require!(amount > 0, EscrowError::InvalidDepositAmount);
 
     let escrow = &mut ctx.accounts.escrow;
+    let mut received = amount;
     
     // Verify depositor is buyer or seller
     require!(
@@ -49,19 +60,30 @@ pub fn handler(ctx: Context<DepositToEscrow>, amount: u64, is_base: bool) -> Res
     );
 
     if is_base {
-        // Transfer base tokens (SPL) to vault
-        let cpi_accounts = Transfer {
+        // Transfer base tokens (SPL or Token-2022) to vault, forwarding any
+        // accounts a transfer hook needs
+        let balance_before = ctx.accounts.base_vault.amount;
+        let cpi_accounts = TransferChecked {
             from: ctx.accounts.depositor_base_account.to_account_info(),
+            mint: ctx.accounts.base_mint.to_account_info(),
             to: ctx.accounts.base_vault.to_account_info(),
             authority: ctx.accounts.depositor.to_account_info(),
         };
-        token::transfer(
-            CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts),
-            amount
+        token_interface::transfer_checked(
+            CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts)
+                .with_remaining_accounts(ctx.remaining_accounts.to_vec()),
+            amount,
+            ctx.accounts.base_mint.decimals
         )?;
 
+        // Record what arrived, which is less than `amount` for mints with a transfer fee
+        ctx.accounts.base_vault.reload()?;
+        received = ctx.accounts.base_vault.amount
+            .checked_sub(balance_before)
+            .ok_or(EscrowError::Overflow)?;
+
         escrow.base_deposited = escrow.base_deposited
-            .checked_add(amount)
+            .checked_add(received)
             .ok_or(EscrowError::Overflow)?;
     } else {
         // Transfer SOL to vault
@@ -89,7 +111,7 @@ pub fn handler(ctx: Context<DepositToEscrow>, amount: u64, is_base: bool) -> Res
 
     msg!(
         "Deposited {} {} to escrow {}",
-        amount,
+        received,
         if is_base { "base tokens" } else { "SOL" },
         escrow.trade_id
     );
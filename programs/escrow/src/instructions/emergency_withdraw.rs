@@ -1,5 +1,5 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
 use crate::constants::*;
 use crate::error::EscrowError;
 use crate::state::*;
@@ -23,15 +23,18 @@ pub struct EmergencyWithdraw<'info> {
     pub authority: Signer<'info>,
     
     #[account(mut)]
-    pub vault: Account<'info, TokenAccount>,
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(address = escrow.base_mint)]
+    pub base_mint: InterfaceAccount<'info, Mint>,
     
     #[account(mut)]
-    pub destination: Account<'info, TokenAccount>,
+    pub destination: InterfaceAccount<'info, TokenAccount>,
     
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
 }
 
-pub fn handler(ctx: Context<EmergencyWithdraw>) -> Result<()> {
+pub fn handler<'info>(ctx: Context<'_, '_, 'info, 'info, EmergencyWithdraw<'info>>) -> Result<()> {
     let escrow = &ctx.accounts.escrow;
     
     // Emergency withdrawal only for stuck funds
@@ -50,18 +53,21 @@ pub fn handler(ctx: Context<EmergencyWithdraw>) -> Result<()> {
             &[escrow.bump],
         ];
 
-        let transfer = Transfer {
+        let transfer = TransferChecked {
             from: ctx.accounts.vault.to_account_info(),
+            mint: ctx.accounts.base_mint.to_account_info(),
             to: ctx.accounts.destination.to_account_info(),
             authority: escrow.to_account_info(),
         };
-        token::transfer(
+        token_interface::transfer_checked(
             CpiContext::new_with_signer(
                 ctx.accounts.token_program.to_account_info(),
                 transfer,
                 &[signer_seeds]
-            ),
-            balance
+            )
+            .with_remaining_accounts(ctx.remaining_accounts.to_vec()),
+            balance,
+            ctx.accounts.base_mint.decimals
         )?;
 
         msg!("Emergency withdrawal: {} tokens from escrow {}", balance, escrow.trade_id);
@@ -1,5 +1,5 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
 use crate::constants::*;
 use crate::error::EscrowError;
 use crate::state::*;
@@ -19,7 +19,10 @@ pub struct ExecuteSwap<'info> {
         mut,
         constraint = base_vault.key() == escrow.base_vault
     )]
-    pub base_vault: Account<'info, TokenAccount>,
+    pub base_vault: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(address = escrow.base_mint)]
+    pub base_mint: InterfaceAccount<'info, Mint>,
     
     /// CHECK: SOL vault PDA
     #[account(
@@ -32,7 +35,7 @@ pub struct ExecuteSwap<'info> {
         mut,
         constraint = buyer_base_account.owner == escrow.buyer
     )]
-    pub buyer_base_account: Account<'info, TokenAccount>,
+    pub buyer_base_account: InterfaceAccount<'info, TokenAccount>,
     
     /// CHECK: Seller receives SOL to their wallet
     #[account(
@@ -44,11 +47,11 @@ pub struct ExecuteSwap<'info> {
     /// CHECK: Can be called by anyone once escrow is funded
     pub executor: Signer<'info>,
     
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
     pub system_program: Program<'info, System>,
 }
 
-pub fn handler(ctx: Context<ExecuteSwap>) -> Result<()> {
+pub fn handler<'info>(ctx: Context<'_, '_, 'info, 'info, ExecuteSwap<'info>>) -> Result<()> {
     let escrow = &mut ctx.accounts.escrow;
     
     require!(escrow.is_fully_funded(), EscrowError::NotFullyFunded);
@@ -62,18 +65,21 @@ pub fn handler(ctx: Context<ExecuteSwap>) -> Result<()> {
     ];
 
     // Transfer base tokens to buyer
-    let base_transfer = Transfer {
+    let base_transfer = TransferChecked {
         from: ctx.accounts.base_vault.to_account_info(),
+        mint: ctx.accounts.base_mint.to_account_info(),
         to: ctx.accounts.buyer_base_account.to_account_info(),
         authority: escrow.to_account_info(),
     };
-    token::transfer(
+    token_interface::transfer_checked(
         CpiContext::new_with_signer(
             ctx.accounts.token_program.to_account_info(),
             base_transfer,
             &[signer_seeds]
-        ),
-        escrow.base_amount
+        )
+        .with_remaining_accounts(ctx.remaining_accounts.to_vec()),
+        escrow.base_amount,
+        ctx.accounts.base_mint.decimals
     )?;
 
     // Transfer SOL to seller
@@ -1,5 +1,5 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{Mint, Token, TokenAccount};
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 use crate::constants::*;
 use crate::error::EscrowError;
 use crate::state::*;
@@ -19,17 +19,18 @@ pub struct InitializeEscrow<'info> {
     pub buyer: SystemAccount<'info>,
     pub seller: SystemAccount<'info>,
     
-    pub base_mint: Account<'info, Mint>,
+    pub base_mint: InterfaceAccount<'info, Mint>,
     
     #[account(
         init,
         payer = initializer,
         token::mint = base_mint,
         token::authority = escrow,
+        token::token_program = token_program,
         seeds = [VAULT_SEED, escrow.key().as_ref(), b"base"],
         bump
     )]
-    pub base_vault: Account<'info, TokenAccount>,
+    pub base_vault: InterfaceAccount<'info, TokenAccount>,
     
     /// CHECK: SOL vault PDA for holding SOL
     #[account(
@@ -44,7 +45,7 @@ pub struct InitializeEscrow<'info> {
     #[account(mut)]
     pub initializer: Signer<'info>,
     
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
     pub system_program: Program<'info, System>,
     pub rent: Sysvar<'info, Rent>,
 }
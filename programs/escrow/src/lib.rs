@@ -24,8 +24,8 @@ pub mod escrow {
     }
 
     /// Deposit tokens into escrow
-    pub fn deposit_to_escrow(
-        ctx: Context<DepositToEscrow>,
+    pub fn deposit_to_escrow<'info>(
+        ctx: Context<'_, '_, 'info, 'info, DepositToEscrow<'info>>,
         amount: u64,
         is_base: bool,
     ) -> Result<()> {
@@ -33,12 +33,16 @@ pub mod escrow {
     }
 
     /// Execute atomic swap from escrow
-    pub fn execute_swap(ctx: Context<ExecuteSwap>) -> Result<()> {
+    pub fn execute_swap<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ExecuteSwap<'info>>,
+    ) -> Result<()> {
         instructions::execute_swap::handler(ctx)
     }
 
     /// Cancel escrow and return funds
-    pub fn cancel_escrow(ctx: Context<CancelEscrow>) -> Result<()> {
+    pub fn cancel_escrow<'info>(
+        ctx: Context<'_, '_, 'info, 'info, CancelEscrow<'info>>,
+    ) -> Result<()> {
         instructions::cancel_escrow::handler(ctx)
     }
 
@@ -48,7 +52,9 @@ pub mod escrow {
     }
 
     /// Emergency withdrawal (admin only)
-    pub fn emergency_withdraw(ctx: Context<EmergencyWithdraw>) -> Result<()> {
+    pub fn emergency_withdraw<'info>(
+        ctx: Context<'_, '_, 'info, 'info, EmergencyWithdraw<'info>>,
+    ) -> Result<()> {
         instructions::emergency_withdraw::handler(ctx)
     }
 }
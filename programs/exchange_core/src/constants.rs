@@ -3,16 +3,34 @@ use anchor_lang::prelude::*;
 // PDA Seeds
 pub const EXCHANGE_SEED: &[u8] = b"exchange";
 pub const ORDER_BOOK_SEED: &[u8] = b"order_book";
-pub const ORDER_SEED: &[u8] = b"order";
+pub const BIDS_SEED: &[u8] = b"bids";
+pub const ASKS_SEED: &[u8] = b"asks";
+pub const EVENT_QUEUE_SEED: &[u8] = b"event_queue";
 pub const TRADING_ACCOUNT_SEED: &[u8] = b"trading_account";
 pub const TRADE_SEED: &[u8] = b"trade";
 pub const VAULT_SEED: &[u8] = b"vault";
+pub const TRIGGER_ORDER_SEED: &[u8] = b"trigger_order";
+pub const OPEN_ORDERS_SEED: &[u8] = b"open_orders";
+pub const TRADING_CALENDAR_SEED: &[u8] = b"trading_calendar";
+pub const COMPLIANCE_REGISTRY_SEED: &[u8] = b"compliance_registry";
+pub const KYC_ENTRY_SEED: &[u8] = b"kyc_entry";
+pub const LISTING_AUTHORITY_SEED: &[u8] = b"listing_authority"; // per base mint, under the listing program
 
 // Constraints
 pub const MAX_FEE_BPS: u16 = 1000; // 10% maximum fee
 pub const MIN_TICK_SIZE: u64 = 1;
 pub const MIN_ORDER_SIZE: u64 = 1;
 pub const MAX_CRANK_ITERATIONS: u8 = 10;
+pub const SLAB_CAPACITY: usize = 64; // resting orders per side of a book
+pub const EVENT_QUEUE_CAPACITY: usize = 64;
+pub const MAX_CONSUME_EVENTS: u16 = 16;
+pub const MAX_PRUNE_ORDERS: u8 = 16;
+pub const TRIGGER_KEEPER_REWARD: u64 = 10_000; // lamports per activated trigger order
+pub const CIRCUIT_BREAKER_COOLDOWN: i64 = 15 * 60; // seconds a market halts after a band breach
+pub const MAX_HOLIDAYS: usize = 64;
+pub const MAX_UTC_OFFSET: i32 = 14 * 60 * 60;
+pub const SECONDS_PER_DAY: i64 = 24 * 60 * 60;
+pub const MAX_COMPLIANCE_OFFICERS: usize = 16;
 
 // Fee tiers based on trading volume
 pub const FEE_TIER_RETAIL: u8 = 0;
@@ -21,5 +39,6 @@ pub const FEE_TIER_VOLUME_2: u8 = 2;
 pub const FEE_TIER_VIP: u8 = 3;
 
 // Decimals
-pub const PRICE_DECIMALS: u32 = 6;
+pub const PRICE_DECIMALS: u32 = 6; // prices are quote lots per base lot in fixed point
+pub const PRICE_SCALE: u64 = 10u64.pow(PRICE_DECIMALS);
 pub const BPS_DENOMINATOR: u64 = 10000;
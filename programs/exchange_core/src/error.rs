@@ -41,6 +41,12 @@ pub enum ExchangeError {
     #[msg("Post-only order would match")]
     PostOnlyWouldMatch,
 
+    #[msg("Fill-or-kill order cannot be completely filled")]
+    FillOrKillNotFilled,
+
+    #[msg("Order type not supported by this instruction")]
+    InvalidOrderType,
+
     #[msg("Market order cannot be fully filled")]
     MarketOrderCannotBeFilled,
 
@@ -58,4 +64,97 @@ pub enum ExchangeError {
 
     #[msg("Unauthorized access")]
     Unauthorized,
+
+    #[msg("Order book side is full")]
+    OrderBookFull,
+
+    #[msg("Invalid trade account")]
+    InvalidTradeAccount,
+
+    #[msg("Event queue is full")]
+    EventQueueFull,
+
+    #[msg("Event queue still has unconsumed events")]
+    EventQueueNotEmpty,
+
+    #[msg("Order expiry must be in the future")]
+    InvalidExpiry,
+
+    #[msg("Refund account does not belong to the order owner")]
+    InvalidRefundAccount,
+
+    #[msg("Trigger price has not been reached")]
+    TriggerNotReached,
+
+    #[msg("Token account mint does not match the market")]
+    InvalidMint,
+
+    #[msg("Lot sizes must be non-zero and every tick a whole number of quote tokens")]
+    InvalidLotSize,
+
+    #[msg("Price is outside the market's price band")]
+    PriceOutsideBand,
+
+    #[msg("Price band cannot exceed 100%")]
+    InvalidPriceBand,
+
+    #[msg("Market is halted by its circuit breaker")]
+    MarketHalted,
+
+    #[msg("Instruction is not allowed in the current trading session")]
+    MarketClosed,
+
+    #[msg("Invalid session schedule")]
+    InvalidSessionSchedule,
+
+    #[msg("Too many holidays")]
+    TooManyHolidays,
+
+    #[msg("Order book is in a call auction")]
+    AuctionInProgress,
+
+    #[msg("Order book is not in a call auction")]
+    NoAuctionInProgress,
+
+    #[msg("Market is halted for a corporate action")]
+    CorporateActionInProgress,
+
+    #[msg("Corporate action needs a whole share multiplier of at least 2 that keeps the tick size valid")]
+    InvalidCorporateAction,
+
+    #[msg("Market has unsettled trades")]
+    UnsettledTrades,
+
+    #[msg("Too many compliance officers")]
+    TooManyComplianceOfficers,
+
+    #[msg("Signer is not a compliance officer")]
+    NotComplianceOfficer,
+
+    #[msg("Wallet does not have valid, unexpired KYC")]
+    KycRequired,
+
+    #[msg("KYC expiry must be in the future")]
+    InvalidKycExpiry,
+
+    #[msg("Jurisdiction must be an ISO 3166-1 alpha-2 country code")]
+    InvalidJurisdiction,
+
+    #[msg("KYC entry has been revoked")]
+    KycRevoked,
+
+    #[msg("Market sell proceeds are below the minimum")]
+    MinimumProceedsNotMet,
+
+    #[msg("Modified order would cross the book")]
+    ModifiedOrderWouldCross,
+
+    #[msg("Wallet still has valid KYC")]
+    TraderNotBlocked,
+
+    #[msg("Market has open trigger orders")]
+    TriggerOrdersOpen,
+
+    #[msg("Invalid lot, tick, minimum order size or price band settings")]
+    InvalidMarketParams,
 }
@@ -0,0 +1,32 @@
+use crate::constants::*;
+use crate::error::ExchangeError;
+use crate::state::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct AcceptAuthority<'info> {
+    #[account(
+        mut,
+        seeds = [EXCHANGE_SEED],
+        bump = exchange.bump,
+        constraint = exchange.pending_authority == new_authority.key() @ ExchangeError::Unauthorized,
+    )]
+    pub exchange: Account<'info, Exchange>,
+
+    pub new_authority: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<AcceptAuthority>) -> Result<()> {
+    let exchange = &mut ctx.accounts.exchange;
+    let previous_authority = exchange.authority;
+    exchange.authority = exchange.pending_authority;
+    exchange.pending_authority = Pubkey::default();
+
+    msg!(
+        "Exchange authority transferred from {} to {}",
+        previous_authority,
+        exchange.authority
+    );
+
+    Ok(())
+}
@@ -0,0 +1,216 @@
+use crate::constants::*;
+use crate::error::ExchangeError;
+use crate::matching::*;
+use crate::state::*;
+use crate::vault::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct ActivateTriggerOrder<'info> {
+    #[account(
+        seeds = [EXCHANGE_SEED],
+        bump = exchange.bump,
+    )]
+    pub exchange: Account<'info, Exchange>,
+
+    #[account(
+        seeds = [TRADING_CALENDAR_SEED],
+        bump = trading_calendar.bump,
+    )]
+    pub trading_calendar: Account<'info, TradingCalendar>,
+
+    #[account(
+        mut,
+        constraint = order_book.exchange == exchange.key() @ ExchangeError::Unauthorized,
+        constraint = order_book.is_active @ ExchangeError::OrderBookInactive,
+        constraint = !order_book.is_restating() @ ExchangeError::CorporateActionInProgress,
+        has_one = bids,
+        has_one = asks,
+        has_one = event_queue,
+    )]
+    pub order_book: Account<'info, OrderBook>,
+
+    #[account(mut)]
+    pub bids: AccountLoader<'info, OrderSlab>,
+
+    #[account(mut)]
+    pub asks: AccountLoader<'info, OrderSlab>,
+
+    #[account(mut)]
+    pub event_queue: AccountLoader<'info, EventQueue>,
+
+    #[account(
+        mut,
+        close = trader,
+        has_one = order_book,
+        has_one = trader,
+    )]
+    pub trigger_order: Account<'info, TriggerOrder>,
+
+    /// CHECK: Owner of the trigger order, receives the account rent
+    #[account(mut)]
+    pub trader: AccountInfo<'info>,
+
+    #[account(
+        seeds = [KYC_ENTRY_SEED, trader.key().as_ref()],
+        bump = kyc_entry.bump,
+    )]
+    pub kyc_entry: Account<'info, KycEntry>,
+
+    #[account(
+        mut,
+        seeds = [OPEN_ORDERS_SEED, order_book.key().as_ref(), trader.key().as_ref()],
+        bump = open_orders.bump,
+    )]
+    pub open_orders: Account<'info, OpenOrders>,
+
+    /// Anyone can activate a triggered order and collect the keeper reward
+    #[account(mut)]
+    pub keeper: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<ActivateTriggerOrder>) -> Result<()> {
+    require!(!ctx.accounts.exchange.paused, ExchangeError::ExchangePaused);
+
+    let trigger = (*ctx.accounts.trigger_order).clone();
+    let last_price = ctx.accounts.order_book.last_price;
+    require!(
+        trigger.is_triggered(last_price),
+        ExchangeError::TriggerNotReached
+    );
+
+    let now = Clock::get()?.unix_timestamp;
+    require!(
+        ctx.accounts.kyc_entry.is_valid(now),
+        ExchangeError::KycRequired
+    );
+    require!(
+        ctx.accounts.trading_calendar.phase(now).allows_matching(),
+        ExchangeError::MarketClosed
+    );
+
+    let market = trigger.order_type == OrderType::Market;
+    let order_book = &mut ctx.accounts.order_book;
+    require!(!order_book.is_halted(now), ExchangeError::MarketHalted);
+    require!(!order_book.in_auction, ExchangeError::AuctionInProgress);
+    require!(
+        market || order_book.within_band(trigger.limit_price),
+        ExchangeError::PriceOutsideBand
+    );
+    order_book.trigger_order_count = order_book
+        .trigger_order_count
+        .checked_sub(1)
+        .ok_or(ExchangeError::Overflow)?;
+    let order_id = order_book.next_order_id;
+    order_book.next_order_id = order_id.checked_add(1).ok_or(ExchangeError::Overflow)?;
+
+    let mut event_queue = ctx.accounts.event_queue.load_mut()?;
+    event_queue.push(Event {
+        event_type: EventType::Trigger.to_u8(),
+        taker_side: trigger.side.to_u8(),
+        maker: trigger.trader,
+        maker_order_id: order_id,
+        maker_client_order_id: trigger.client_order_id,
+        price: trigger.trigger_price,
+        quantity: trigger.quantity,
+        timestamp: now,
+        ..bytemuck::Zeroable::zeroed()
+    })?;
+
+    // Match exactly as a freshly placed limit or market order would
+    let taker = Taker {
+        order_id,
+        client_order_id: trigger.client_order_id,
+        trader: trigger.trader,
+        side: trigger.side,
+        limit_price: (!market).then_some(trigger.limit_price),
+        quantity: trigger.quantity,
+        quote_budget: (market && trigger.side == OrderSide::Bid).then_some(trigger.locked_amount),
+        self_trade_behavior: trigger.self_trade_behavior,
+    };
+    let (makers, resting) = match trigger.side {
+        OrderSide::Bid => (&ctx.accounts.asks, &ctx.accounts.bids),
+        OrderSide::Ask => (&ctx.accounts.bids, &ctx.accounts.asks),
+    };
+    let outcome = match_taker(
+        order_book,
+        &mut *makers.load_mut()?,
+        &mut event_queue,
+        &ctx.accounts.exchange,
+        &taker,
+        now,
+    )?;
+    drop(event_queue);
+
+    let resting_quantity = if market || outcome.taker_cancelled {
+        0
+    } else {
+        outcome.taker_remaining(&taker)
+    };
+
+    // Keep what the fills and the resting remainder need and unlock the rest
+    let used = match trigger.side {
+        OrderSide::Bid => outcome.quote_amount,
+        OrderSide::Ask => order_book.base_amount(outcome.base_filled)?,
+    }
+    .checked_add(order_book.locked_amount(trigger.side, trigger.limit_price, resting_quantity)?)
+    .ok_or(ExchangeError::Overflow)?;
+    let excess = trigger
+        .locked_amount
+        .checked_sub(used)
+        .ok_or(ExchangeError::InsufficientFunds)?;
+
+    let open_orders = &mut ctx.accounts.open_orders;
+    open_orders.unlock(trigger.side, excess)?;
+
+    // Unlock whatever the trader's own cancelled resting orders had locked
+    open_orders.unlock(trigger.side.opposite(), outcome.maker_refund)?;
+
+    if resting_quantity > 0 {
+        let (_, evicted) = resting.load_mut()?.insert(OrderNode {
+            order_id,
+            client_order_id: trigger.client_order_id,
+            trader: trigger.trader,
+            price: trigger.limit_price,
+            quantity: trigger.quantity - outcome.self_trade_decrement,
+            filled_quantity: outcome.base_filled,
+            timestamp: now,
+            expires_at: 0,
+        })?;
+
+        let order_book = &mut ctx.accounts.order_book;
+        order_book.total_orders = order_book
+            .total_orders
+            .checked_add(1)
+            .ok_or(ExchangeError::Overflow)?;
+
+        // A full side drops its worst order to make room for a better one
+        if let Some(evicted) = evicted {
+            evict_order(
+                order_book,
+                &mut *ctx.accounts.event_queue.load_mut()?,
+                trigger.side,
+                &evicted,
+                now,
+            )?;
+        }
+    }
+
+    // Pay the keeper; the remaining rent goes back to the trader when the account closes
+    move_lamports(
+        &ctx.accounts.trigger_order.to_account_info(),
+        &ctx.accounts.keeper.to_account_info(),
+        trigger.keeper_reward,
+    )?;
+
+    msg!(
+        "Trigger order {} activated at last price {} as order {}: Filled {}, Resting {}",
+        trigger.trigger_id,
+        last_price,
+        order_id,
+        outcome.base_filled,
+        resting_quantity
+    );
+
+    Ok(())
+}
@@ -0,0 +1,106 @@
+use crate::constants::*;
+use crate::error::ExchangeError;
+use crate::state::*;
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, Mint, MintTo, TokenAccount, TokenInterface};
+
+#[derive(Accounts)]
+pub struct ApplyCorporateAction<'info> {
+    #[account(
+        seeds = [EXCHANGE_SEED],
+        bump = exchange.bump,
+        has_one = authority @ ExchangeError::Unauthorized,
+    )]
+    pub exchange: Account<'info, Exchange>,
+
+    #[account(
+        mut,
+        constraint = order_book.exchange == exchange.key() @ ExchangeError::Unauthorized,
+        constraint = !order_book.is_restating() @ ExchangeError::CorporateActionInProgress,
+        has_one = base_mint,
+        has_one = base_vault,
+        has_one = bids,
+        has_one = asks,
+        has_one = event_queue,
+    )]
+    pub order_book: Account<'info, OrderBook>,
+
+    #[account(mut)]
+    pub base_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(mut)]
+    pub base_vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub bids: AccountLoader<'info, OrderSlab>,
+
+    #[account(mut)]
+    pub asks: AccountLoader<'info, OrderSlab>,
+
+    pub event_queue: AccountLoader<'info, EventQueue>,
+
+    pub authority: Signer<'info>,
+
+    /// Mint authority of the base token, which issues the new shares
+    pub mint_authority: Signer<'info>,
+
+    pub base_token_program: Interface<'info, TokenInterface>,
+}
+
+/// Restate the market for a split or bonus issue giving `share_multiplier`
+/// new shares per old share: resting orders and the market's tick size, minimum
+/// size and prices are rescaled here, then the market stays halted until
+/// `rescale_accounts` has restated every open orders and trigger order account.
+///
+/// The base vault backs every balance and order on the market, so it is
+/// topped up here to `share_multiplier` times its balance in new shares.
+pub fn handler(ctx: Context<ApplyCorporateAction>, share_multiplier: u64) -> Result<()> {
+    let order_book = &mut ctx.accounts.order_book;
+    require!(
+        ctx.accounts.event_queue.load()?.count == 0,
+        ExchangeError::EventQueueNotEmpty
+    );
+    require!(
+        order_book.unsettled_trades == 0,
+        ExchangeError::UnsettledTrades
+    );
+    order_book.restate(share_multiplier)?;
+
+    let balance_before = ctx.accounts.base_vault.amount;
+    let expected = balance_before
+        .checked_mul(share_multiplier)
+        .ok_or(ExchangeError::Overflow)?;
+    token_interface::mint_to(
+        CpiContext::new(
+            ctx.accounts.base_token_program.to_account_info(),
+            MintTo {
+                mint: ctx.accounts.base_mint.to_account_info(),
+                to: ctx.accounts.base_vault.to_account_info(),
+                authority: ctx.accounts.mint_authority.to_account_info(),
+            },
+        ),
+        expected - balance_before,
+    )?;
+    ctx.accounts.base_vault.reload()?;
+    require!(
+        ctx.accounts.base_vault.amount == expected,
+        ExchangeError::InvalidCorporateAction
+    );
+
+    for slab in [&ctx.accounts.bids, &ctx.accounts.asks] {
+        let mut slab = slab.load_mut()?;
+        let count = slab.count as usize;
+        for node in &mut slab.nodes[..count] {
+            node.rescale(share_multiplier)?;
+        }
+    }
+
+    msg!(
+        "Corporate action applied to order book {}: {} shares per share, {} accounts to rescale",
+        order_book.key(),
+        share_multiplier,
+        order_book.pending_rescales
+    );
+
+    Ok(())
+}
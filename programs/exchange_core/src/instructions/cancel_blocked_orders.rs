@@ -0,0 +1,81 @@
+use crate::constants::*;
+use crate::error::ExchangeError;
+use crate::matching::*;
+use crate::state::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct CancelBlockedOrders<'info> {
+    #[account(
+        mut,
+        constraint = order_book.is_active @ ExchangeError::OrderBookInactive,
+        constraint = !order_book.is_restating() @ ExchangeError::CorporateActionInProgress,
+        has_one = bids,
+        has_one = asks,
+        has_one = event_queue,
+    )]
+    pub order_book: Account<'info, OrderBook>,
+
+    #[account(mut)]
+    pub bids: AccountLoader<'info, OrderSlab>,
+
+    #[account(mut)]
+    pub asks: AccountLoader<'info, OrderSlab>,
+
+    #[account(mut)]
+    pub event_queue: AccountLoader<'info, EventQueue>,
+
+    #[account(
+        seeds = [KYC_ENTRY_SEED, kyc_entry.wallet.as_ref()],
+        bump = kyc_entry.bump,
+    )]
+    pub kyc_entry: Account<'info, KycEntry>,
+
+    #[account(
+        mut,
+        seeds = [OPEN_ORDERS_SEED, order_book.key().as_ref(), kyc_entry.wallet.as_ref()],
+        bump = open_orders.bump,
+    )]
+    pub open_orders: Account<'info, OpenOrders>,
+
+    /// Anyone can pull a blocked wallet's orders
+    pub cranker: Signer<'info>,
+}
+
+/// Take every resting order of a wallet whose KYC was suspended, revoked or
+/// has expired off the book, so they stop matching against other traders.
+/// Their collateral goes back to the wallet's free balance.
+pub fn handler(ctx: Context<CancelBlockedOrders>) -> Result<()> {
+    let timestamp = Clock::get()?.unix_timestamp;
+    require!(
+        !ctx.accounts.kyc_entry.is_valid(timestamp),
+        ExchangeError::TraderNotBlocked
+    );
+
+    let wallet = ctx.accounts.kyc_entry.wallet;
+    let order_book = &mut ctx.accounts.order_book;
+    let open_orders = &mut ctx.accounts.open_orders;
+    let mut event_queue = ctx.accounts.event_queue.load_mut()?;
+    let mut cancelled: u32 = 0;
+
+    for slab in [&ctx.accounts.bids, &ctx.accounts.asks] {
+        let mut slab = slab.load_mut()?;
+        cancelled += cancel_trader_orders(
+            &mut slab,
+            order_book,
+            open_orders,
+            &mut event_queue,
+            &wallet,
+            timestamp,
+        )?;
+    }
+
+    msg!(
+        "Cancelled {} orders of blocked wallet {} on order book {}",
+        cancelled,
+        wallet,
+        order_book.key()
+    );
+
+    Ok(())
+}
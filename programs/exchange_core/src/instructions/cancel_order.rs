@@ -2,117 +2,89 @@ use crate::constants::*;
 use crate::error::ExchangeError;
 use crate::state::*;
 use anchor_lang::prelude::*;
-use anchor_lang::system_program;
-use anchor_spl::token::{self, Token, TokenAccount, Transfer};
 
 #[derive(Accounts)]
-#[instruction(order_id: u64)]
 pub struct CancelOrder<'info> {
     #[account(
         mut,
         constraint = order_book.is_active @ ExchangeError::OrderBookInactive,
+        constraint = !order_book.is_restating() @ ExchangeError::CorporateActionInProgress,
+        has_one = bids,
+        has_one = asks,
+        has_one = event_queue,
     )]
     pub order_book: Account<'info, OrderBook>,
 
-    #[account(
-        mut,
-        seeds = [
-            ORDER_SEED,
-            order_book.key().as_ref(),
-            order_id.to_le_bytes().as_ref()
-        ],
-        bump,
-        constraint = order.trader == trader.key() @ ExchangeError::UnauthorizedOrderModification,
-        constraint = order.is_active @ ExchangeError::CannotCancelInactiveOrder,
-    )]
-    pub order: Account<'info, Order>,
-
     #[account(mut)]
-    pub trader: Signer<'info>,
+    pub bids: AccountLoader<'info, OrderSlab>,
 
     #[account(mut)]
-    pub trader_base_account: Account<'info, TokenAccount>,
+    pub asks: AccountLoader<'info, OrderSlab>,
 
     #[account(mut)]
-    pub base_vault: Account<'info, TokenAccount>,
+    pub event_queue: AccountLoader<'info, EventQueue>,
+
+    pub trader: Signer<'info>,
 
-    /// CHECK: SOL vault PDA for holding quote currency (SOL)
     #[account(
         mut,
-        seeds = [b"sol_vault", order_book.key().as_ref()],
-        bump,
+        seeds = [OPEN_ORDERS_SEED, order_book.key().as_ref(), trader.key().as_ref()],
+        bump = open_orders.bump,
     )]
-    pub sol_vault: AccountInfo<'info>,
-
-    pub token_program: Program<'info, Token>,
-    pub system_program: Program<'info, System>,
+    pub open_orders: Account<'info, OpenOrders>,
 }
 
 pub fn handler(ctx: Context<CancelOrder>, order_id: u64) -> Result<()> {
-    let order = &mut ctx.accounts.order;
-
-    require!(order.is_active, ExchangeError::CannotCancelInactiveOrder);
+    // Take the order off whichever side of the book holds it
+    let (side, order) = {
+        let mut bids = ctx.accounts.bids.load_mut()?;
+        let mut asks = ctx.accounts.asks.load_mut()?;
+        if let Some(index) = bids.find(order_id) {
+            require_keys_eq!(
+                bids.nodes[index].trader,
+                ctx.accounts.trader.key(),
+                ExchangeError::UnauthorizedOrderModification
+            );
+            (OrderSide::Bid, bids.remove(index))
+        } else if let Some(index) = asks.find(order_id) {
+            require_keys_eq!(
+                asks.nodes[index].trader,
+                ctx.accounts.trader.key(),
+                ExchangeError::UnauthorizedOrderModification
+            );
+            (OrderSide::Ask, asks.remove(index))
+        } else {
+            return err!(ExchangeError::OrderNotFound);
+        }
+    };
 
     // Calculate unfilled quantity
-    let unfilled_quantity = order
-        .quantity
-        .checked_sub(order.filled_quantity)
-        .ok_or(ExchangeError::Overflow)?;
-
-    // Return locked tokens/SOL to trader based on order side
-    if unfilled_quantity > 0 {
-        match order.side {
-            OrderSide::Ask => {
-                // Return base tokens for sell order
-                let base_mint = ctx.accounts.order_book.base_mint;
-                let bump = ctx.accounts.order_book.bump;
-                
-                let signer_seeds = &[
-                    ORDER_BOOK_SEED,
-                    base_mint.as_ref(),
-                    &[bump],
-                ];
-
-                let cpi_accounts = Transfer {
-                    from: ctx.accounts.base_vault.to_account_info(),
-                    to: ctx.accounts.trader_base_account.to_account_info(),
-                    authority: ctx.accounts.order_book.to_account_info(),
-                };
-
-                token::transfer(
-                    CpiContext::new_with_signer(
-                        ctx.accounts.token_program.to_account_info(),
-                        cpi_accounts,
-                        &[signer_seeds],
-                    ),
-                    unfilled_quantity,
-                )?;
-            }
-            OrderSide::Bid => {
-                // Return SOL for buy order
-                let sol_amount = order
-                    .price
-                    .checked_mul(unfilled_quantity)
-                    .ok_or(ExchangeError::Overflow)?;
-
-                **ctx.accounts.sol_vault.try_borrow_mut_lamports()? = ctx
-                    .accounts
-                    .sol_vault
-                    .lamports()
-                    .checked_sub(sol_amount)
-                    .ok_or(ExchangeError::InsufficientFunds)?;
-                **ctx.accounts.trader.try_borrow_mut_lamports()? = ctx
-                    .accounts
-                    .trader
-                    .lamports()
-                    .checked_add(sol_amount)
-                    .ok_or(ExchangeError::Overflow)?;
-            }
-        }
+    let unfilled_quantity = order.remaining_quantity();
+
+    // The out event only informs queue readers; nothing is left to settle, so
+    // a full queue never blocks a cancel
+    let mut event_queue = ctx.accounts.event_queue.load_mut()?;
+    if !event_queue.is_full() {
+        event_queue.push(Event {
+            event_type: EventType::Out.to_u8(),
+            taker_side: side.to_u8(),
+            maker: order.trader,
+            maker_order_id: order_id,
+            maker_client_order_id: order.client_order_id,
+            price: order.price,
+            quantity: unfilled_quantity,
+            timestamp: Clock::get()?.unix_timestamp,
+            ..bytemuck::Zeroable::zeroed()
+        })?;
     }
-
-    // Mark order as inactive
-    order.is_active = false;
+    drop(event_queue);
+
+    // Return the locked collateral to the trader's free balance
+    let released = ctx
+        .accounts
+        .order_book
+        .locked_amount(side, order.price, unfilled_quantity)?;
+    ctx.accounts.open_orders.unlock(side, released)?;
 
     // Update order book statistics
     let order_book = &mut ctx.accounts.order_book;
@@ -0,0 +1,31 @@
+use crate::error::ExchangeError;
+use crate::instructions::cancel_order::{self, CancelOrder};
+use anchor_lang::prelude::*;
+
+/// Uses the same accounts as `cancel_order`; the order is looked up among the
+/// signer's own resting orders.
+pub fn handler(ctx: Context<CancelOrder>, client_order_id: u64) -> Result<()> {
+    // 0 marks orders placed without a client id
+    require!(client_order_id != 0, ExchangeError::OrderNotFound);
+
+    let trader = ctx.accounts.trader.key();
+    let order_id = {
+        let bids = ctx.accounts.bids.load()?;
+        let asks = ctx.accounts.asks.load()?;
+        bids.find_by_client_id(&trader, client_order_id)
+            .map(|index| bids.nodes[index].order_id)
+            .or_else(|| {
+                asks.find_by_client_id(&trader, client_order_id)
+                    .map(|index| asks.nodes[index].order_id)
+            })
+            .ok_or(ExchangeError::OrderNotFound)?
+    };
+
+    msg!(
+        "Cancelling order {} by client ID {}",
+        order_id,
+        client_order_id
+    );
+
+    cancel_order::handler(ctx, order_id)
+}
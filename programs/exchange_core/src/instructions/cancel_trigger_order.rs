@@ -0,0 +1,55 @@
+use crate::constants::*;
+use crate::error::ExchangeError;
+use crate::state::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct CancelTriggerOrder<'info> {
+    #[account(
+        mut,
+        constraint = !order_book.is_restating() @ ExchangeError::CorporateActionInProgress,
+    )]
+    pub order_book: Account<'info, OrderBook>,
+
+    // Closing returns the rent and the unused keeper reward
+    #[account(
+        mut,
+        close = trader,
+        has_one = order_book,
+        has_one = trader @ ExchangeError::UnauthorizedOrderModification,
+    )]
+    pub trigger_order: Account<'info, TriggerOrder>,
+
+    #[account(mut)]
+    pub trader: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [OPEN_ORDERS_SEED, order_book.key().as_ref(), trader.key().as_ref()],
+        bump = open_orders.bump,
+    )]
+    pub open_orders: Account<'info, OpenOrders>,
+}
+
+pub fn handler(ctx: Context<CancelTriggerOrder>) -> Result<()> {
+    let order_book = &mut ctx.accounts.order_book;
+    order_book.trigger_order_count = order_book
+        .trigger_order_count
+        .checked_sub(1)
+        .ok_or(ExchangeError::Overflow)?;
+
+    let trigger_order = &ctx.accounts.trigger_order;
+
+    // Return the locked collateral to the free balance
+    ctx.accounts
+        .open_orders
+        .unlock(trigger_order.side, trigger_order.locked_amount)?;
+
+    msg!(
+        "Trigger order {} cancelled, returned locked amount: {}",
+        trigger_order.trigger_id,
+        trigger_order.locked_amount
+    );
+
+    Ok(())
+}
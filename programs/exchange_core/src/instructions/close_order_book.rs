@@ -15,20 +15,48 @@ pub struct CloseOrderBook<'info> {
     #[account(
         mut,
         constraint = order_book.exchange == exchange.key() @ ExchangeError::Unauthorized,
+        has_one = bids,
+        has_one = asks,
+        has_one = event_queue,
     )]
     pub order_book: Account<'info, OrderBook>,
 
+    // Order storage rent goes back to the authority that paid for it
+    #[account(mut, close = authority)]
+    pub bids: AccountLoader<'info, OrderSlab>,
+
+    #[account(mut, close = authority)]
+    pub asks: AccountLoader<'info, OrderSlab>,
+
+    #[account(mut, close = authority)]
+    pub event_queue: AccountLoader<'info, EventQueue>,
+
+    #[account(mut)]
     pub authority: Signer<'info>,
 }
 
+/// The order book account itself stays open, marked inactive: it owns the
+/// vaults and traders' open orders accounts, and signs their withdrawals.
 pub fn handler(ctx: Context<CloseOrderBook>) -> Result<()> {
+    // Ensure nothing left needs the slabs or the queue: no resting or trigger
+    // orders, unconsumed events or trades waiting to settle
     let order_book = &mut ctx.accounts.order_book;
-
-    // Ensure no active orders before closing
     require!(
         order_book.total_orders == 0,
         ExchangeError::OrderBookInactive
     );
+    require!(
+        order_book.trigger_order_count == 0,
+        ExchangeError::TriggerOrdersOpen
+    );
+    require!(
+        order_book.unsettled_trades == 0,
+        ExchangeError::UnsettledTrades
+    );
+    require!(
+        ctx.accounts.event_queue.load()?.count == 0,
+        ExchangeError::EventQueueNotEmpty
+    );
 
     order_book.is_active = false;
 
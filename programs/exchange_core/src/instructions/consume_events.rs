@@ -0,0 +1,158 @@
+use crate::constants::*;
+use crate::error::ExchangeError;
+use crate::state::*;
+use crate::vault::*;
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+
+#[derive(Accounts)]
+pub struct ConsumeEvents<'info> {
+    #[account(
+        mut,
+        has_one = event_queue,
+    )]
+    pub order_book: Account<'info, OrderBook>,
+
+    #[account(mut)]
+    pub event_queue: AccountLoader<'info, EventQueue>,
+
+    /// Anyone can drain the queue; the cranker pays rent for the trade records
+    #[account(mut)]
+    pub cranker: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Remaining accounts, one per event in queue order: an uninitialized trade PDA
+/// for each fill (in `next_trade_id` order) and the owner's open orders account
+/// for each expiry or eviction. Consumption stops early once they run out.
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, ConsumeEvents<'info>>,
+    limit: u16,
+) -> Result<()> {
+    require!(limit <= MAX_CONSUME_EVENTS, ExchangeError::Overflow);
+
+    let order_book_key = ctx.accounts.order_book.key();
+    let mut event_queue = ctx.accounts.event_queue.load_mut()?;
+    let mut event_accounts = ctx.remaining_accounts.iter();
+    let mut consumed: u16 = 0;
+
+    while consumed < limit {
+        let Some(event) = event_queue.front().copied() else {
+            break;
+        };
+
+        // Each fill becomes a trade record crediting both parties until settled
+        if event.event_type().is_fill() {
+            let Some(trade_account) = event_accounts.next() else {
+                break;
+            };
+
+            let order_book = &mut ctx.accounts.order_book;
+            let trade_id = order_book.next_trade_id;
+            create_trade_account(
+                trade_account,
+                &ctx.accounts.cranker,
+                &ctx.accounts.system_program,
+                &order_book_key,
+                trade_id,
+            )?;
+
+            let trade = Trade {
+                order_book: order_book_key,
+                trade_id,
+                maker_order_id: event.maker_order_id,
+                taker_order_id: event.taker_order_id,
+                maker: event.maker,
+                taker: event.taker,
+                taker_side: event.taker_side(),
+                price: event.price,
+                quantity: event.quantity,
+                maker_fee: event.maker_fee,
+                taker_fee: event.taker_fee,
+                price_improvement: event.price_improvement,
+                timestamp: event.timestamp,
+                rent_payer: ctx.accounts.cranker.key(),
+            };
+            trade.try_serialize(&mut &mut trade_account.try_borrow_mut_data()?[..])?;
+
+            order_book.next_trade_id = trade_id.checked_add(1).ok_or(ExchangeError::Overflow)?;
+            order_book.unsettled_trades = order_book
+                .unsettled_trades
+                .checked_add(1)
+                .ok_or(ExchangeError::Overflow)?;
+        }
+
+        // Orders expired or evicted by the exchange still hold their collateral
+        if event.event_type().is_refund() {
+            let Some(refund_account) = event_accounts.next() else {
+                break;
+            };
+
+            let amount = ctx.accounts.order_book.locked_amount(
+                event.taker_side(),
+                event.price,
+                event.quantity,
+            )?;
+            refund_order(
+                &order_book_key,
+                event.taker_side(),
+                &event.maker,
+                amount,
+                refund_account,
+            )?;
+        }
+
+        event_queue.pop_front();
+        consumed += 1;
+    }
+
+    msg!(
+        "Consumed {} events for order book {} ({} remaining)",
+        consumed,
+        order_book_key,
+        event_queue.count
+    );
+
+    Ok(())
+}
+
+fn create_trade_account<'info>(
+    trade_account: &AccountInfo<'info>,
+    payer: &Signer<'info>,
+    system_program: &Program<'info, System>,
+    order_book_key: &Pubkey,
+    trade_id: u64,
+) -> Result<()> {
+    let trade_id_bytes = trade_id.to_le_bytes();
+    let (expected, bump) = Pubkey::find_program_address(
+        &[TRADE_SEED, order_book_key.as_ref(), trade_id_bytes.as_ref()],
+        &crate::ID,
+    );
+    require_keys_eq!(
+        trade_account.key(),
+        expected,
+        ExchangeError::InvalidTradeAccount
+    );
+
+    let signer_seeds: &[&[u8]] = &[
+        TRADE_SEED,
+        order_book_key.as_ref(),
+        trade_id_bytes.as_ref(),
+        &[bump],
+    ];
+
+    system_program::create_account(
+        CpiContext::new_with_signer(
+            system_program.to_account_info(),
+            system_program::CreateAccount {
+                from: payer.to_account_info(),
+                to: trade_account.clone(),
+            },
+            &[signer_seeds],
+        ),
+        Rent::get()?.minimum_balance(Trade::LEN),
+        Trade::LEN as u64,
+        &crate::ID,
+    )
+}
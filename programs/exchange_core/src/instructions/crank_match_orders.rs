@@ -1,13 +1,43 @@
 use crate::constants::*;
 use crate::error::ExchangeError;
+use crate::matching::*;
 use crate::state::*;
 use anchor_lang::prelude::*;
 
 #[derive(Accounts)]
 pub struct CrankMatchOrders<'info> {
-    #[account(mut)]
+    #[account(
+        seeds = [EXCHANGE_SEED],
+        bump = exchange.bump,
+    )]
+    pub exchange: Account<'info, Exchange>,
+
+    #[account(
+        seeds = [TRADING_CALENDAR_SEED],
+        bump = trading_calendar.bump,
+    )]
+    pub trading_calendar: Account<'info, TradingCalendar>,
+
+    #[account(
+        mut,
+        constraint = order_book.exchange == exchange.key() @ ExchangeError::Unauthorized,
+        constraint = order_book.is_active @ ExchangeError::OrderBookInactive,
+        constraint = !order_book.is_restating() @ ExchangeError::CorporateActionInProgress,
+        has_one = bids,
+        has_one = asks,
+        has_one = event_queue,
+    )]
     pub order_book: Account<'info, OrderBook>,
 
+    #[account(mut)]
+    pub bids: AccountLoader<'info, OrderSlab>,
+
+    #[account(mut)]
+    pub asks: AccountLoader<'info, OrderSlab>,
+
+    #[account(mut)]
+    pub event_queue: AccountLoader<'info, EventQueue>,
+
     /// CHECK: Cranker account that calls the matching engine
     pub cranker: Signer<'info>,
 }
@@ -17,30 +47,96 @@ pub fn handler(ctx: Context<CrankMatchOrders>, max_iterations: u8) -> Result<()>
         max_iterations <= MAX_CRANK_ITERATIONS,
         ExchangeError::Overflow
     );
+    require!(!ctx.accounts.exchange.paused, ExchangeError::ExchangePaused);
 
+    let timestamp = Clock::get()?.unix_timestamp;
+    require!(
+        ctx.accounts
+            .trading_calendar
+            .phase(timestamp)
+            .allows_matching(),
+        ExchangeError::MarketClosed
+    );
+
+    let exchange = &ctx.accounts.exchange;
     let order_book = &mut ctx.accounts.order_book;
-    require!(order_book.is_active, ExchangeError::OrderBookInactive);
+    require!(
+        !order_book.is_halted(timestamp),
+        ExchangeError::MarketHalted
+    );
+    require!(!order_book.in_auction, ExchangeError::AuctionInProgress);
+    let mut bids = ctx.accounts.bids.load_mut()?;
+    let mut asks = ctx.accounts.asks.load_mut()?;
+    let mut event_queue = ctx.accounts.event_queue.load_mut()?;
+    let mut fills: u8 = 0;
+
+    while fills < max_iterations && !event_queue.is_full() {
+        let (Some(bid), Some(ask)) = (bids.best().copied(), asks.best().copied()) else {
+            break;
+        };
+        if bid.price < ask.price {
+            break;
+        }
+        if bid.is_expired(timestamp) {
+            expire_best(&mut bids, order_book, &mut event_queue, timestamp)?;
+            continue;
+        }
+        if ask.is_expired(timestamp) {
+            expire_best(&mut asks, order_book, &mut event_queue, timestamp)?;
+            continue;
+        }
+        // Crossed orders from one trader are never traded; the newer one is cancelled
+        if bid.trader == ask.trader {
+            msg!(
+                "Self-trade prevented: bid {} and ask {} belong to the same trader",
+                bid.order_id,
+                ask.order_id
+            );
+            if bid.order_id < ask.order_id {
+                evict_best(&mut asks, order_book, &mut event_queue, timestamp)?;
+            } else {
+                evict_best(&mut bids, order_book, &mut event_queue, timestamp)?;
+            }
+            continue;
+        }
 
-    // In a full implementation, this would:
-    // 1. Load the top bid and ask orders
-    // 2. Check if they can match (bid price >= ask price)
-    // 3. Execute the trade atomically
-    // 4. Update both orders (filled quantity, etc.)
-    // 5. Create trade records
-    // 6. Repeat for max_iterations or until no more matches
+        // The older order was resting first, so it is the maker and sets the price
+        let quantity = bid.remaining_quantity().min(ask.remaining_quantity());
+        let (maker, taker) = if bid.order_id < ask.order_id {
+            (bid, Taker::from_resting(&ask, OrderSide::Ask))
+        } else {
+            (ask, Taker::from_resting(&bid, OrderSide::Bid))
+        };
+        if !order_book.within_band(maker.price) {
+            order_book.halt(timestamp);
+            break;
+        }
+
+        // A newer bid locked quote at its own, higher price; the excess goes back on settlement
+        let price_improvement = order_book.quote_amount(bid.price, quantity)?
+            - order_book.quote_amount(maker.price, quantity)?;
+
+        record_fill(
+            order_book,
+            &mut event_queue,
+            exchange,
+            &maker,
+            &taker,
+            quantity,
+            price_improvement,
+            timestamp,
+        )?;
+        fill_best(&mut bids, order_book, quantity)?;
+        fill_best(&mut asks, order_book, quantity)?;
+
+        fills += 1;
+    }
 
-    // Simplified version - just log the crank operation
     msg!(
-        "Crank matching executed for order book: {} (max iterations: {})",
+        "Crank matching executed for order book: {} ({} fills)",
         order_book.key(),
-        max_iterations
+        fills
     );
 
-    // This is a placeholder. A real implementation would need:
-    // - Access to bid and ask order accounts
-    // - Token transfer logic for settlements
-    // - Fee calculations and distributions
-    // - Trade event emissions
-
     Ok(())
 }
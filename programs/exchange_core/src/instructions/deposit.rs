@@ -0,0 +1,141 @@
+use crate::constants::*;
+use crate::error::ExchangeError;
+use crate::state::*;
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
+
+#[derive(Accounts)]
+pub struct Deposit<'info> {
+    #[account(
+        constraint = order_book.is_active @ ExchangeError::OrderBookInactive,
+        constraint = !order_book.is_restating() @ ExchangeError::CorporateActionInProgress,
+    )]
+    pub order_book: Account<'info, OrderBook>,
+
+    #[account(
+        mut,
+        seeds = [OPEN_ORDERS_SEED, order_book.key().as_ref(), owner.key().as_ref()],
+        bump = open_orders.bump,
+    )]
+    pub open_orders: Account<'info, OpenOrders>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = owner_base_account.owner == owner.key() @ ExchangeError::Unauthorized,
+        constraint = owner_base_account.mint == order_book.base_mint @ ExchangeError::InvalidMint,
+    )]
+    pub owner_base_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        address = order_book.base_vault,
+    )]
+    pub base_vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = owner_quote_account.owner == owner.key() @ ExchangeError::Unauthorized,
+        constraint = owner_quote_account.mint == order_book.quote_mint @ ExchangeError::InvalidMint,
+    )]
+    pub owner_quote_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        address = order_book.quote_vault,
+    )]
+    pub quote_vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(address = order_book.base_mint)]
+    pub base_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(address = order_book.quote_mint)]
+    pub quote_mint: InterfaceAccount<'info, Mint>,
+
+    pub base_token_program: Interface<'info, TokenInterface>,
+    pub quote_token_program: Interface<'info, TokenInterface>,
+}
+
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, Deposit<'info>>,
+    base_amount: u64,
+    quote_amount: u64,
+) -> Result<()> {
+    require!(
+        base_amount > 0 || quote_amount > 0,
+        ExchangeError::InvalidQuantity
+    );
+
+    let base_received = deposit_tokens(
+        &ctx.accounts.owner_base_account,
+        &mut ctx.accounts.base_vault,
+        &ctx.accounts.base_mint,
+        &ctx.accounts.owner,
+        &ctx.accounts.base_token_program,
+        ctx.remaining_accounts,
+        base_amount,
+    )?;
+    let quote_received = deposit_tokens(
+        &ctx.accounts.owner_quote_account,
+        &mut ctx.accounts.quote_vault,
+        &ctx.accounts.quote_mint,
+        &ctx.accounts.owner,
+        &ctx.accounts.quote_token_program,
+        ctx.remaining_accounts,
+        quote_amount,
+    )?;
+
+    let open_orders = &mut ctx.accounts.open_orders;
+    open_orders.credit_base(base_received)?;
+    open_orders.credit_quote(quote_received)?;
+
+    msg!(
+        "Deposited {} base and {} quote: free balances {} base, {} quote",
+        base_received,
+        quote_received,
+        open_orders.base_free,
+        open_orders.quote_free
+    );
+
+    Ok(())
+}
+
+/// Move `amount` from the owner into a vault and return what actually arrived,
+/// which is less than `amount` for mints with a Token-2022 transfer fee
+fn deposit_tokens<'info>(
+    from: &InterfaceAccount<'info, TokenAccount>,
+    vault: &mut InterfaceAccount<'info, TokenAccount>,
+    mint: &InterfaceAccount<'info, Mint>,
+    owner: &Signer<'info>,
+    token_program: &Interface<'info, TokenInterface>,
+    extra_accounts: &[AccountInfo<'info>],
+    amount: u64,
+) -> Result<u64> {
+    if amount == 0 {
+        return Ok(0);
+    }
+
+    let balance_before = vault.amount;
+    token_interface::transfer_checked(
+        CpiContext::new(
+            token_program.to_account_info(),
+            TransferChecked {
+                from: from.to_account_info(),
+                mint: mint.to_account_info(),
+                to: vault.to_account_info(),
+                authority: owner.to_account_info(),
+            },
+        )
+        .with_remaining_accounts(extra_accounts.to_vec()),
+        amount,
+        mint.decimals,
+    )?;
+    vault.reload()?;
+
+    vault
+        .amount
+        .checked_sub(balance_before)
+        .ok_or(ExchangeError::Overflow.into())
+}
@@ -0,0 +1,47 @@
+use crate::constants::*;
+use crate::error::ExchangeError;
+use crate::state::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct InitializeComplianceRegistry<'info> {
+    #[account(
+        seeds = [EXCHANGE_SEED],
+        bump = exchange.bump,
+        has_one = authority @ ExchangeError::Unauthorized,
+    )]
+    pub exchange: Account<'info, Exchange>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = ComplianceRegistry::LEN,
+        seeds = [COMPLIANCE_REGISTRY_SEED],
+        bump
+    )]
+    pub compliance_registry: Account<'info, ComplianceRegistry>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<InitializeComplianceRegistry>, officers: Vec<Pubkey>) -> Result<()> {
+    require!(
+        officers.len() <= MAX_COMPLIANCE_OFFICERS,
+        ExchangeError::TooManyComplianceOfficers
+    );
+
+    let compliance_registry = &mut ctx.accounts.compliance_registry;
+    compliance_registry.exchange = ctx.accounts.exchange.key();
+    compliance_registry.officers = officers;
+    compliance_registry.bump = ctx.bumps.compliance_registry;
+
+    msg!(
+        "Compliance registry initialized with {} officers",
+        compliance_registry.officers.len()
+    );
+
+    Ok(())
+}
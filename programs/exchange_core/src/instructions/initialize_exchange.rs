@@ -35,6 +35,8 @@ pub fn handler(
 
     let exchange = &mut ctx.accounts.exchange;
     exchange.authority = ctx.accounts.authority.key();
+    exchange.pending_authority = Pubkey::default();
+    exchange.listing_program = Pubkey::default();
     exchange.fee_collector = ctx.accounts.fee_collector.key();
     exchange.maker_fee_bps = maker_fee_bps;
     exchange.taker_fee_bps = taker_fee_bps;
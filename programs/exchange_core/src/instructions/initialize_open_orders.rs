@@ -0,0 +1,54 @@
+use crate::constants::*;
+use crate::error::ExchangeError;
+use crate::state::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct InitializeOpenOrders<'info> {
+    #[account(
+        mut,
+        constraint = !order_book.is_restating() @ ExchangeError::CorporateActionInProgress,
+    )]
+    pub order_book: Account<'info, OrderBook>,
+
+    #[account(
+        init,
+        payer = owner,
+        space = OpenOrders::LEN,
+        seeds = [OPEN_ORDERS_SEED, order_book.key().as_ref(), owner.key().as_ref()],
+        bump
+    )]
+    pub open_orders: Account<'info, OpenOrders>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<InitializeOpenOrders>) -> Result<()> {
+    let order_book = &mut ctx.accounts.order_book;
+    order_book.open_orders_count = order_book
+        .open_orders_count
+        .checked_add(1)
+        .ok_or(ExchangeError::Overflow)?;
+
+    let open_orders = &mut ctx.accounts.open_orders;
+
+    open_orders.order_book = order_book.key();
+    open_orders.owner = ctx.accounts.owner.key();
+    open_orders.base_free = 0;
+    open_orders.base_locked = 0;
+    open_orders.quote_free = 0;
+    open_orders.quote_locked = 0;
+    open_orders.share_epoch = order_book.share_epoch;
+    open_orders.bump = ctx.bumps.open_orders;
+
+    msg!(
+        "Open orders account initialized for {} on order book {}",
+        ctx.accounts.owner.key(),
+        ctx.accounts.order_book.key()
+    );
+
+    Ok(())
+}
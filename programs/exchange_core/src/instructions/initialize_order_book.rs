@@ -2,7 +2,7 @@ use crate::constants::*;
 use crate::error::ExchangeError;
 use crate::state::*;
 use anchor_lang::prelude::*;
-use anchor_spl::token::{Mint, Token, TokenAccount};
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 
 #[derive(Accounts)]
 #[instruction(base_mint: Pubkey)]
@@ -11,29 +11,33 @@ pub struct InitializeOrderBook<'info> {
         mut,
         seeds = [EXCHANGE_SEED],
         bump = exchange.bump,
-        has_one = authority
+        constraint = exchange.may_list(&authority.key(), &base_mint.key()) @ ExchangeError::Unauthorized,
     )]
     pub exchange: Account<'info, Exchange>,
 
     #[account(
         init,
-        payer = authority,
+        payer = payer,
         space = OrderBook::LEN,
         seeds = [
             ORDER_BOOK_SEED,
             base_mint.key().as_ref(),
+            quote_mint.key().as_ref(),
         ],
         bump
     )]
     pub order_book: Account<'info, OrderBook>,
 
-    pub base_mint: Account<'info, Mint>,
+    pub base_mint: InterfaceAccount<'info, Mint>,
+
+    pub quote_mint: InterfaceAccount<'info, Mint>,
 
     #[account(
         init,
-        payer = authority,
+        payer = payer,
         token::mint = base_mint,
         token::authority = order_book,
+        token::token_program = base_token_program,
         seeds = [
             VAULT_SEED,
             order_book.key().as_ref(),
@@ -41,22 +45,58 @@ pub struct InitializeOrderBook<'info> {
         ],
         bump
     )]
-    pub base_vault: Account<'info, TokenAccount>,
+    pub base_vault: InterfaceAccount<'info, TokenAccount>,
 
-    /// CHECK: SOL vault PDA for holding quote currency (SOL)
     #[account(
         init,
-        payer = authority,
-        space = 0,
-        seeds = [b"sol_vault", order_book.key().as_ref()],
+        payer = payer,
+        token::mint = quote_mint,
+        token::authority = order_book,
+        token::token_program = quote_token_program,
+        seeds = [
+            VAULT_SEED,
+            order_book.key().as_ref(),
+            b"quote"
+        ],
         bump
     )]
-    pub sol_vault: AccountInfo<'info>,
+    pub quote_vault: InterfaceAccount<'info, TokenAccount>,
 
-    #[account(mut)]
+    #[account(
+        init,
+        payer = payer,
+        space = OrderSlab::LEN,
+        seeds = [BIDS_SEED, order_book.key().as_ref()],
+        bump
+    )]
+    pub bids: AccountLoader<'info, OrderSlab>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = OrderSlab::LEN,
+        seeds = [ASKS_SEED, order_book.key().as_ref()],
+        bump
+    )]
+    pub asks: AccountLoader<'info, OrderSlab>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = EventQueue::LEN,
+        seeds = [EVENT_QUEUE_SEED, order_book.key().as_ref()],
+        bump
+    )]
+    pub event_queue: AccountLoader<'info, EventQueue>,
+
+    /// Exchange authority, or the listing program's authority for `base_mint`
     pub authority: Signer<'info>,
 
-    pub token_program: Program<'info, Token>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub base_token_program: Interface<'info, TokenInterface>,
+    pub quote_token_program: Interface<'info, TokenInterface>,
     pub system_program: Program<'info, System>,
     pub rent: Sysvar<'info, Rent>,
 }
@@ -64,38 +104,71 @@ pub struct InitializeOrderBook<'info> {
 pub fn handler(
     ctx: Context<InitializeOrderBook>,
     base_mint: Pubkey,
-    tick_size: u64,
-    min_order_size: u64,
+    params: MarketParams,
+    reference_price: u64,
 ) -> Result<()> {
-    require!(tick_size >= MIN_TICK_SIZE, ExchangeError::InvalidPrice);
-    require!(
-        min_order_size >= MIN_ORDER_SIZE,
-        ExchangeError::InvalidQuantity
-    );
+    require!(params.is_valid(), ExchangeError::InvalidMarketParams);
+    let MarketParams {
+        base_lot_size,
+        quote_lot_size,
+        tick_size,
+        min_order_size,
+        price_band_bps,
+    } = params;
     require!(!ctx.accounts.exchange.paused, ExchangeError::ExchangePaused);
 
     let order_book = &mut ctx.accounts.order_book;
     order_book.exchange = ctx.accounts.exchange.key();
     order_book.base_mint = base_mint;
     order_book.base_vault = ctx.accounts.base_vault.key();
-    order_book.sol_vault = ctx.accounts.sol_vault.key();
+    order_book.quote_mint = ctx.accounts.quote_mint.key();
+    order_book.quote_vault = ctx.accounts.quote_vault.key();
+    order_book.bids = ctx.accounts.bids.key();
+    order_book.asks = ctx.accounts.asks.key();
+    order_book.event_queue = ctx.accounts.event_queue.key();
+    order_book.base_lot_size = base_lot_size;
+    order_book.quote_lot_size = quote_lot_size;
     order_book.tick_size = tick_size;
     order_book.min_order_size = min_order_size;
-    order_book.bids_head = None;
-    order_book.asks_head = None;
     order_book.next_order_id = 1;
+    order_book.next_trade_id = 1;
     order_book.total_orders = 0;
     order_book.total_volume = 0;
     order_book.last_price = 0;
+    order_book.reference_price = reference_price;
+    order_book.price_band_bps = price_band_bps;
+    order_book.halted_until = 0;
+    order_book.in_auction = false;
+    order_book.auction_price = 0;
+    order_book.open_orders_count = 0;
+    order_book.trigger_order_count = 0;
+    order_book.unsettled_trades = 0;
+    order_book.share_epoch = 0;
+    order_book.pending_rescales = 0;
+    order_book.share_multiplier = 1;
     order_book.is_active = true;
     order_book.bump = ctx.bumps.order_book;
 
+    let order_book_key = order_book.key();
+    for (slab, side) in [
+        (&ctx.accounts.bids, OrderSide::Bid),
+        (&ctx.accounts.asks, OrderSide::Ask),
+    ] {
+        let mut slab = slab.load_init()?;
+        slab.order_book = order_book_key;
+        slab.side = side.to_u8();
+    }
+    ctx.accounts.event_queue.load_init()?.order_book = order_book_key;
+
     let exchange = &mut ctx.accounts.exchange;
     exchange.total_markets = exchange.total_markets.checked_add(1).unwrap();
 
     msg!(
-        "Order book initialized for {} with SOL as quote currency, tick size: {}, min order: {}",
+        "Order book initialized for {}/{}, lot sizes: {} base, {} quote, tick size: {}, min order: {}",
         base_mint,
+        ctx.accounts.quote_mint.key(),
+        base_lot_size,
+        quote_lot_size,
         tick_size,
         min_order_size
     );
@@ -1,4 +1,5 @@
 use crate::constants::*;
+use crate::error::ExchangeError;
 use crate::state::*;
 use anchor_lang::prelude::*;
 
@@ -22,10 +23,23 @@ pub struct InitializeTradingAccount<'info> {
     #[account(mut)]
     pub owner: Signer<'info>,
 
+    #[account(
+        seeds = [KYC_ENTRY_SEED, owner.key().as_ref()],
+        bump = kyc_entry.bump,
+    )]
+    pub kyc_entry: Account<'info, KycEntry>,
+
     pub system_program: Program<'info, System>,
 }
 
 pub fn handler(ctx: Context<InitializeTradingAccount>) -> Result<()> {
+    require!(
+        ctx.accounts
+            .kyc_entry
+            .is_valid(Clock::get()?.unix_timestamp),
+        ExchangeError::KycRequired
+    );
+
     let trading_account = &mut ctx.accounts.trading_account;
 
     trading_account.owner = ctx.accounts.owner.key();
@@ -0,0 +1,54 @@
+use crate::constants::*;
+use crate::error::ExchangeError;
+use crate::state::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct InitializeTradingCalendar<'info> {
+    #[account(
+        seeds = [EXCHANGE_SEED],
+        bump = exchange.bump,
+        has_one = authority @ ExchangeError::Unauthorized,
+    )]
+    pub exchange: Account<'info, Exchange>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = TradingCalendar::LEN,
+        seeds = [TRADING_CALENDAR_SEED],
+        bump
+    )]
+    pub trading_calendar: Account<'info, TradingCalendar>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(
+    ctx: Context<InitializeTradingCalendar>,
+    schedule: SessionSchedule,
+    holidays: Vec<i64>,
+) -> Result<()> {
+    require!(schedule.is_valid(), ExchangeError::InvalidSessionSchedule);
+    require!(
+        holidays.len() <= MAX_HOLIDAYS,
+        ExchangeError::TooManyHolidays
+    );
+
+    let trading_calendar = &mut ctx.accounts.trading_calendar;
+    trading_calendar.exchange = ctx.accounts.exchange.key();
+    trading_calendar.schedule = schedule;
+    trading_calendar.holidays = holidays;
+    trading_calendar.bump = ctx.bumps.trading_calendar;
+
+    msg!(
+        "Trading calendar initialized: UTC offset {}s, {} holidays",
+        schedule.utc_offset,
+        trading_calendar.holidays.len()
+    );
+
+    Ok(())
+}
@@ -1,21 +1,72 @@
+pub mod accept_authority;
+pub mod activate_trigger_order;
+pub mod apply_corporate_action;
+pub mod cancel_blocked_orders;
 pub mod cancel_order;
+pub mod cancel_order_by_client_id;
+pub mod cancel_trigger_order;
 pub mod close_order_book;
+pub mod consume_events;
 pub mod crank_match_orders;
+pub mod deposit;
+pub mod initialize_compliance_registry;
 pub mod initialize_exchange;
+pub mod initialize_open_orders;
 pub mod initialize_order_book;
 pub mod initialize_trading_account;
+pub mod initialize_trading_calendar;
 pub mod modify_order;
+pub mod open_auction;
 pub mod place_limit_order;
 pub mod place_market_order;
+pub mod place_trigger_order;
+pub mod propose_authority;
+pub mod prune_expired_orders;
+pub mod register_investor;
+pub mod reinstate_investor;
+pub mod rescale_accounts;
+pub mod revoke_investor;
+pub mod set_compliance_officers;
+pub mod set_paused;
+pub mod set_price_band;
+pub mod settle_funds;
 pub mod settle_trade;
+pub mod suspend_investor;
+pub mod uncross_auction;
+pub mod update_exchange;
+pub mod update_investor;
+pub mod update_trading_calendar;
+pub mod withdraw;
 
+pub use accept_authority::*;
+pub use activate_trigger_order::*;
+pub use apply_corporate_action::*;
+pub use cancel_blocked_orders::*;
 pub use cancel_order::*;
+pub use cancel_trigger_order::*;
 pub use close_order_book::*;
+pub use consume_events::*;
 pub use crank_match_orders::*;
+pub use deposit::*;
+pub use initialize_compliance_registry::*;
 pub use initialize_exchange::*;
+pub use initialize_open_orders::*;
 pub use initialize_order_book::*;
 pub use initialize_trading_account::*;
+pub use initialize_trading_calendar::*;
 pub use modify_order::*;
+pub use open_auction::*;
 pub use place_limit_order::*;
 pub use place_market_order::*;
+pub use place_trigger_order::*;
+pub use prune_expired_orders::*;
+pub use register_investor::*;
+pub use rescale_accounts::*;
+pub use set_compliance_officers::*;
+pub use set_price_band::*;
 pub use settle_trade::*;
+pub use uncross_auction::*;
+pub use update_exchange::*;
+pub use update_investor::*;
+pub use update_trading_calendar::*;
+pub use withdraw::*;
@@ -6,45 +6,111 @@ use anchor_lang::prelude::*;
 #[derive(Accounts)]
 pub struct ModifyOrder<'info> {
     #[account(
-        mut,
-        constraint = order_book.is_active @ ExchangeError::OrderBookInactive,
+        seeds = [EXCHANGE_SEED],
+        bump = exchange.bump,
     )]
-    pub order_book: Account<'info, OrderBook>,
+    pub exchange: Account<'info, Exchange>,
+
+    #[account(
+        seeds = [TRADING_CALENDAR_SEED],
+        bump = trading_calendar.bump,
+    )]
+    pub trading_calendar: Account<'info, TradingCalendar>,
 
     #[account(
         mut,
-        constraint = order.trader == trader.key() @ ExchangeError::UnauthorizedOrderModification,
-        constraint = order.is_active @ ExchangeError::CannotCancelInactiveOrder,
-        constraint = order.order_book == order_book.key() @ ExchangeError::Unauthorized,
+        constraint = order_book.exchange == exchange.key() @ ExchangeError::Unauthorized,
+        constraint = order_book.is_active @ ExchangeError::OrderBookInactive,
+        constraint = !order_book.is_restating() @ ExchangeError::CorporateActionInProgress,
+        has_one = bids,
+        has_one = asks,
     )]
-    pub order: Account<'info, Order>,
+    pub order_book: Account<'info, OrderBook>,
 
     #[account(mut)]
+    pub bids: AccountLoader<'info, OrderSlab>,
+
+    #[account(mut)]
+    pub asks: AccountLoader<'info, OrderSlab>,
+
     pub trader: Signer<'info>,
+
+    #[account(
+        seeds = [KYC_ENTRY_SEED, trader.key().as_ref()],
+        bump = kyc_entry.bump,
+    )]
+    pub kyc_entry: Account<'info, KycEntry>,
+
+    #[account(
+        mut,
+        seeds = [OPEN_ORDERS_SEED, order_book.key().as_ref(), trader.key().as_ref()],
+        bump = open_orders.bump,
+    )]
+    pub open_orders: Account<'info, OpenOrders>,
 }
 
+/// `new_quantity` is the order's total size, including anything already filled.
+/// A requeued order gets a new order ID. While the market is matching, a new
+/// price must not cross the other side of the book.
 pub fn handler(
     ctx: Context<ModifyOrder>,
     order_id: u64,
     new_price: Option<u64>,
     new_quantity: Option<u64>,
 ) -> Result<()> {
-    let order = &mut ctx.accounts.order;
-    let order_book = &ctx.accounts.order_book;
+    require!(!ctx.accounts.exchange.paused, ExchangeError::ExchangePaused);
 
-    require!(order.is_active, ExchangeError::OrderNotFound);
+    let now = Clock::get()?.unix_timestamp;
     require!(
-        order.filled_quantity == 0,
-        ExchangeError::OrderAlreadyFilled
+        ctx.accounts.kyc_entry.is_valid(now),
+        ExchangeError::KycRequired
+    );
+    let phase = ctx.accounts.trading_calendar.phase(now);
+    require!(phase.allows_modification(), ExchangeError::MarketClosed);
+
+    let order_book = &mut ctx.accounts.order_book;
+    require!(!order_book.is_halted(now), ExchangeError::MarketHalted);
+    let matching = phase.allows_matching() && !order_book.in_auction;
+    let mut bids = ctx.accounts.bids.load_mut()?;
+    let mut asks = ctx.accounts.asks.load_mut()?;
+
+    let (slab, opposite) = if bids.find(order_id).is_some() {
+        (&mut bids, &asks)
+    } else {
+        (&mut asks, &bids)
+    };
+    let side = slab.side();
+    let index = slab.find(order_id).ok_or(ExchangeError::OrderNotFound)?;
+    let old_order = slab.nodes[index];
+    let mut order = old_order;
+
+    require_keys_eq!(
+        order.trader,
+        ctx.accounts.trader.key(),
+        ExchangeError::UnauthorizedOrderModification
     );
 
     // Update price if provided
     if let Some(price) = new_price {
         require!(price > 0, ExchangeError::InvalidPrice);
         require!(
-            price % order_book.tick_size == 0,
+            price.is_multiple_of(order_book.tick_size),
             ExchangeError::PriceNotAlignedToTickSize
         );
+        require!(
+            order_book.within_band(price),
+            ExchangeError::PriceOutsideBand
+        );
+        // Nothing matches a modified order, so it must not lock the book crossed
+        let crosses = opposite
+            .orders()
+            .iter()
+            .find(|resting| !resting.is_expired(now))
+            .is_some_and(|best| !side.outranks(best.price, price));
+        require!(
+            !matching || !crosses,
+            ExchangeError::ModifiedOrderWouldCross
+        );
         order.price = price;
         msg!("Order {} price updated to {}", order_id, price);
     }
@@ -55,13 +121,40 @@ pub fn handler(
             quantity >= order_book.min_order_size,
             ExchangeError::QuantityBelowMinimum
         );
-        require!(quantity > 0, ExchangeError::InvalidQuantity);
+        require!(
+            quantity > order.filled_quantity,
+            ExchangeError::InvalidQuantity
+        );
         order.quantity = quantity;
         msg!("Order {} quantity updated to {}", order_id, quantity);
     }
 
-    // Update timestamp to reflect modification
-    order.timestamp = Clock::get()?.unix_timestamp;
+    // A pure size reduction keeps its place in the queue; anything else is requeued
+    if order.price == old_order.price && order.quantity <= old_order.quantity {
+        slab.nodes[index] = order;
+    } else {
+        // A fresh ID ranks the requeued order as the newer one when it crosses
+        order.order_id = order_book.next_order_id;
+        order_book.next_order_id = order
+            .order_id
+            .checked_add(1)
+            .ok_or(ExchangeError::Overflow)?;
+        order.timestamp = now;
+        slab.remove(index);
+        slab.insert(order)?;
+        msg!("Order {} requeued as order {}", order_id, order.order_id);
+    }
+
+    // Top up or refund the difference in locked collateral
+    let old_locked =
+        order_book.locked_amount(side, old_order.price, old_order.remaining_quantity())?;
+    let new_locked = order_book.locked_amount(side, order.price, order.remaining_quantity())?;
+    let open_orders = &mut ctx.accounts.open_orders;
+    if new_locked > old_locked {
+        open_orders.lock(side, new_locked - old_locked)?;
+    } else {
+        open_orders.unlock(side, old_locked - new_locked)?;
+    }
 
     Ok(())
 }
@@ -0,0 +1,36 @@
+use crate::constants::*;
+use crate::error::ExchangeError;
+use crate::state::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct OpenAuction<'info> {
+    #[account(
+        seeds = [EXCHANGE_SEED],
+        bump = exchange.bump,
+        has_one = authority @ ExchangeError::Unauthorized,
+    )]
+    pub exchange: Account<'info, Exchange>,
+
+    #[account(
+        mut,
+        constraint = order_book.exchange == exchange.key() @ ExchangeError::Unauthorized,
+        constraint = order_book.is_active @ ExchangeError::OrderBookInactive,
+    )]
+    pub order_book: Account<'info, OrderBook>,
+
+    pub authority: Signer<'info>,
+}
+
+/// Stop continuous matching so orders accumulate until `uncross_auction`
+pub fn handler(ctx: Context<OpenAuction>) -> Result<()> {
+    let order_book = &mut ctx.accounts.order_book;
+    require!(!order_book.in_auction, ExchangeError::AuctionInProgress);
+
+    order_book.in_auction = true;
+    order_book.auction_price = 0;
+
+    msg!("Call auction opened for order book {}", order_book.key());
+
+    Ok(())
+}
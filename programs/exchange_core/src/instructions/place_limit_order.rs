@@ -1,12 +1,21 @@
 use crate::constants::*;
 use crate::error::ExchangeError;
+use crate::matching::*;
 use crate::state::*;
 use anchor_lang::prelude::*;
-use anchor_lang::system_program;
-use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct LimitOrderParams {
+    pub side: OrderSide,
+    pub price: u64,
+    pub quantity: u64,
+    pub order_type: OrderType, // any type but Market
+    pub self_trade_behavior: SelfTradeBehavior,
+    pub client_order_id: Option<u64>,
+    pub expires_at: Option<i64>, // good-till-time, None for good-till-cancelled
+}
 
 #[derive(Accounts)]
-#[instruction(side: OrderSide, price: u64, quantity: u64)]
 pub struct PlaceLimitOrder<'info> {
     #[account(
         seeds = [EXCHANGE_SEED],
@@ -14,25 +23,31 @@ pub struct PlaceLimitOrder<'info> {
     )]
     pub exchange: Account<'info, Exchange>,
 
+    #[account(
+        seeds = [TRADING_CALENDAR_SEED],
+        bump = trading_calendar.bump,
+    )]
+    pub trading_calendar: Account<'info, TradingCalendar>,
+
     #[account(
         mut,
         constraint = order_book.exchange == exchange.key() @ ExchangeError::Unauthorized,
         constraint = order_book.is_active @ ExchangeError::OrderBookInactive,
+        constraint = !order_book.is_restating() @ ExchangeError::CorporateActionInProgress,
+        has_one = bids,
+        has_one = asks,
+        has_one = event_queue,
     )]
     pub order_book: Account<'info, OrderBook>,
 
-    #[account(
-        init,
-        payer = trader,
-        space = Order::LEN,
-        seeds = [
-            ORDER_SEED,
-            order_book.key().as_ref(),
-            order_book.next_order_id.to_le_bytes().as_ref()
-        ],
-        bump
-    )]
-    pub order: Account<'info, Order>,
+    #[account(mut)]
+    pub bids: AccountLoader<'info, OrderSlab>,
+
+    #[account(mut)]
+    pub asks: AccountLoader<'info, OrderSlab>,
+
+    #[account(mut)]
+    pub event_queue: AccountLoader<'info, EventQueue>,
 
     #[account(
         mut,
@@ -42,124 +57,188 @@ pub struct PlaceLimitOrder<'info> {
     )]
     pub trading_account: Account<'info, TradingAccount>,
 
-    #[account(mut)]
     pub trader: Signer<'info>,
 
-    // For base token (SPL token)
     #[account(
-        mut,
-        constraint = trader_base_account.owner == trader.key()
+        seeds = [KYC_ENTRY_SEED, trader.key().as_ref()],
+        bump = kyc_entry.bump,
     )]
-    pub trader_base_account: Account<'info, TokenAccount>,
-
-    #[account(mut)]
-    pub base_vault: Account<'info, TokenAccount>,
+    pub kyc_entry: Account<'info, KycEntry>,
 
-    /// CHECK: SOL vault PDA for holding quote currency (SOL)
     #[account(
         mut,
-        seeds = [b"sol_vault", order_book.key().as_ref()],
-        bump,
+        seeds = [OPEN_ORDERS_SEED, order_book.key().as_ref(), trader.key().as_ref()],
+        bump = open_orders.bump,
     )]
-    pub sol_vault: AccountInfo<'info>,
-
-    pub token_program: Program<'info, Token>,
-    pub system_program: Program<'info, System>,
+    pub open_orders: Account<'info, OpenOrders>,
 }
 
-pub fn handler(
-    ctx: Context<PlaceLimitOrder>,
-    side: OrderSide,
-    price: u64,
-    quantity: u64,
-) -> Result<()> {
+pub fn handler(ctx: Context<PlaceLimitOrder>, params: LimitOrderParams) -> Result<()> {
+    let LimitOrderParams {
+        side,
+        price,
+        quantity,
+        order_type,
+        self_trade_behavior,
+        client_order_id,
+        expires_at,
+    } = params;
     require!(!ctx.accounts.exchange.paused, ExchangeError::ExchangePaused);
+    require!(
+        order_type != OrderType::Market,
+        ExchangeError::InvalidOrderType
+    );
     require!(
         quantity >= ctx.accounts.order_book.min_order_size,
         ExchangeError::QuantityBelowMinimum
     );
     require!(price > 0, ExchangeError::InvalidPrice);
     require!(
-        price % ctx.accounts.order_book.tick_size == 0,
+        price.is_multiple_of(ctx.accounts.order_book.tick_size),
         ExchangeError::PriceNotAlignedToTickSize
     );
 
+    let now = Clock::get()?.unix_timestamp;
+    require!(
+        ctx.accounts.kyc_entry.is_valid(now),
+        ExchangeError::KycRequired
+    );
+    let order_book = &ctx.accounts.order_book;
+    require!(!order_book.is_halted(now), ExchangeError::MarketHalted);
+
+    // Pre-open and call auctions accept orders that can rest; they are not
+    // matched until the session opens or the auction uncrosses
+    let phase = ctx.accounts.trading_calendar.phase(now);
+    require!(phase.allows_order_entry(), ExchangeError::MarketClosed);
+    let matching = phase.allows_matching() && !order_book.in_auction;
+    require!(
+        matching || matches!(order_type, OrderType::Limit | OrderType::PostOnly),
+        ExchangeError::InvalidOrderType
+    );
+    require!(
+        order_book.within_band(price),
+        ExchangeError::PriceOutsideBand
+    );
+
+    let expires_at = expires_at.unwrap_or(0);
+    require!(
+        expires_at == 0 || expires_at > now,
+        ExchangeError::InvalidExpiry
+    );
+
     let order_book = &mut ctx.accounts.order_book;
-    let order = &mut ctx.accounts.order;
     let order_id = order_book.next_order_id;
+    order_book.next_order_id = order_id.checked_add(1).ok_or(ExchangeError::Overflow)?;
 
-    // Calculate required tokens/SOL to lock
-    match side {
-        OrderSide::Ask => {
-            // Lock base tokens (SPL token) for sell order
-            let cpi_accounts = Transfer {
-                from: ctx.accounts.trader_base_account.to_account_info(),
-                to: ctx.accounts.base_vault.to_account_info(),
-                authority: ctx.accounts.trader.to_account_info(),
-            };
-            let cpi_program = ctx.accounts.token_program.to_account_info();
-            let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
-            token::transfer(cpi_ctx, quantity)?;
-        }
-        OrderSide::Bid => {
-            // Lock SOL for buy order
-            let sol_amount = price.checked_mul(quantity).ok_or(ExchangeError::Overflow)?;
-
-            // Transfer SOL from trader to vault
-            system_program::transfer(
-                CpiContext::new(
-                    ctx.accounts.system_program.to_account_info(),
-                    system_program::Transfer {
-                        from: ctx.accounts.trader.to_account_info(),
-                        to: ctx.accounts.sol_vault.to_account_info(),
-                    },
-                ),
-                sol_amount,
-            )?;
-        }
+    let client_order_id = client_order_id.unwrap_or(0);
+    let taker = Taker {
+        order_id,
+        client_order_id,
+        trader: ctx.accounts.trader.key(),
+        side,
+        limit_price: Some(price),
+        quantity,
+        quote_budget: None,
+        self_trade_behavior,
+    };
+    let (makers, resting) = match side {
+        OrderSide::Bid => (&ctx.accounts.asks, &ctx.accounts.bids),
+        OrderSide::Ask => (&ctx.accounts.bids, &ctx.accounts.asks),
+    };
+
+    // Take whatever crosses first; post-only orders must not take at all
+    let outcome = if order_type == OrderType::PostOnly {
+        let would_match = makers
+            .load()?
+            .orders()
+            .iter()
+            .find(|maker| !maker.is_expired(now))
+            .is_some_and(|best| taker.crosses(best.price));
+        require!(!would_match, ExchangeError::PostOnlyWouldMatch);
+        MatchOutcome::default()
+    } else if !matching {
+        MatchOutcome::default()
+    } else {
+        match_taker(
+            order_book,
+            &mut *makers.load_mut()?,
+            &mut *ctx.accounts.event_queue.load_mut()?,
+            &ctx.accounts.exchange,
+            &taker,
+            now,
+        )?
+    };
+
+    if order_type == OrderType::FillOrKill {
+        require!(
+            outcome.base_filled == quantity,
+            ExchangeError::FillOrKillNotFilled
+        );
     }
 
-    // Initialize order
-    order.order_book = order_book.key();
-    order.trader = ctx.accounts.trader.key();
-    order.order_id = order_id;
-    order.side = side;
-    order.order_type = OrderType::Limit;
-    order.price = price;
-    order.quantity = quantity;
-    order.filled_quantity = 0;
-    order.timestamp = Clock::get()?.unix_timestamp;
-    order.next = None;
-    order.prev = None;
-    order.is_active = true;
-
-    // Insert order into linked list (simplified - in production use proper price-time priority)
-    match side {
-        OrderSide::Bid => {
-            order.next = order_book.bids_head;
-            order_book.bids_head = Some(order_id);
+    // Only limit and post-only orders rest; IOC and FOK remainders are cancelled
+    let resting_quantity = match order_type {
+        OrderType::Limit | OrderType::PostOnly if !outcome.taker_cancelled => {
+            outcome.taker_remaining(&taker)
         }
-        OrderSide::Ask => {
-            order.next = order_book.asks_head;
-            order_book.asks_head = Some(order_id);
+        _ => 0,
+    };
+
+    // Lock what the fills settle against plus collateral for the resting remainder
+    let order_book = &ctx.accounts.order_book;
+    let lock_amount = match side {
+        OrderSide::Ask => order_book.base_amount(outcome.base_filled)?,
+        OrderSide::Bid => outcome.quote_amount,
+    }
+    .checked_add(order_book.locked_amount(side, price, resting_quantity)?)
+    .ok_or(ExchangeError::Overflow)?;
+    let open_orders = &mut ctx.accounts.open_orders;
+    open_orders.lock(side, lock_amount)?;
+
+    // Unlock whatever the trader's own cancelled resting orders had locked
+    open_orders.unlock(side.opposite(), outcome.maker_refund)?;
+
+    // Rest the remainder on its side of the book at its price-time position
+    if resting_quantity > 0 {
+        let (_, evicted) = resting.load_mut()?.insert(OrderNode {
+            order_id,
+            client_order_id,
+            trader: ctx.accounts.trader.key(),
+            price,
+            quantity: quantity - outcome.self_trade_decrement,
+            filled_quantity: outcome.base_filled,
+            timestamp: now,
+            expires_at,
+        })?;
+
+        let order_book = &mut ctx.accounts.order_book;
+        order_book.total_orders = order_book
+            .total_orders
+            .checked_add(1)
+            .ok_or(ExchangeError::Overflow)?;
+
+        // A full side drops its worst order to make room for a better one
+        if let Some(evicted) = evicted {
+            evict_order(
+                order_book,
+                &mut *ctx.accounts.event_queue.load_mut()?,
+                side,
+                &evicted,
+                now,
+            )?;
         }
     }
 
-    order_book.next_order_id = order_book
-        .next_order_id
-        .checked_add(1)
-        .ok_or(ExchangeError::Overflow)?;
-    order_book.total_orders = order_book
-        .total_orders
-        .checked_add(1)
-        .ok_or(ExchangeError::Overflow)?;
-
     msg!(
-        "Limit order placed: ID {}, Side {:?}, Price {}, Quantity {}",
+        "Limit order placed: ID {}, Client ID {}, Side {:?}, Type {:?}, Price {}, Quantity {}, Filled {}, Resting {}",
         order_id,
+        client_order_id,
         side,
+        order_type,
         price,
-        quantity
+        quantity,
+        outcome.base_filled,
+        resting_quantity
     );
 
     Ok(())
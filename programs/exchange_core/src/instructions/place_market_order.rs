@@ -1,9 +1,8 @@
 use crate::constants::*;
 use crate::error::ExchangeError;
+use crate::matching::*;
 use crate::state::*;
 use anchor_lang::prelude::*;
-use anchor_lang::system_program;
-use anchor_spl::token::{self, Token, TokenAccount, Transfer};
 
 #[derive(Accounts)]
 pub struct PlaceMarketOrder<'info> {
@@ -13,42 +12,52 @@ pub struct PlaceMarketOrder<'info> {
     )]
     pub exchange: Account<'info, Exchange>,
 
+    #[account(
+        seeds = [TRADING_CALENDAR_SEED],
+        bump = trading_calendar.bump,
+    )]
+    pub trading_calendar: Account<'info, TradingCalendar>,
+
     #[account(
         mut,
         constraint = order_book.exchange == exchange.key() @ ExchangeError::Unauthorized,
         constraint = order_book.is_active @ ExchangeError::OrderBookInactive,
+        constraint = !order_book.is_restating() @ ExchangeError::CorporateActionInProgress,
+        has_one = bids,
+        has_one = asks,
+        has_one = event_queue,
     )]
     pub order_book: Account<'info, OrderBook>,
 
+    #[account(mut)]
+    pub bids: AccountLoader<'info, OrderSlab>,
+
+    #[account(mut)]
+    pub asks: AccountLoader<'info, OrderSlab>,
+
+    #[account(mut)]
+    pub event_queue: AccountLoader<'info, EventQueue>,
+
     #[account(
-        mut,
         seeds = [TRADING_ACCOUNT_SEED, trader.key().as_ref()],
         bump = trading_account.bump,
     )]
     pub trading_account: Account<'info, TradingAccount>,
 
-    #[account(mut)]
     pub trader: Signer<'info>,
 
     #[account(
-        mut,
-        constraint = trader_base_account.owner == trader.key()
+        seeds = [KYC_ENTRY_SEED, trader.key().as_ref()],
+        bump = kyc_entry.bump,
     )]
-    pub trader_base_account: Account<'info, TokenAccount>,
+    pub kyc_entry: Account<'info, KycEntry>,
 
-    #[account(mut)]
-    pub base_vault: Account<'info, TokenAccount>,
-
-    /// CHECK: SOL vault PDA for holding quote currency (SOL)
     #[account(
         mut,
-        seeds = [b"sol_vault", order_book.key().as_ref()],
-        bump,
+        seeds = [OPEN_ORDERS_SEED, order_book.key().as_ref(), trader.key().as_ref()],
+        bump = open_orders.bump,
     )]
-    pub sol_vault: AccountInfo<'info>,
-
-    pub token_program: Program<'info, Token>,
-    pub system_program: Program<'info, System>,
+    pub open_orders: Account<'info, OpenOrders>,
 }
 
 pub fn handler(
@@ -56,112 +65,86 @@ pub fn handler(
     side: OrderSide,
     quantity: u64,
     max_quote_amount: u64,
+    min_quote_amount: u64,
+    self_trade_behavior: SelfTradeBehavior,
+    client_order_id: Option<u64>,
 ) -> Result<()> {
     require!(!ctx.accounts.exchange.paused, ExchangeError::ExchangePaused);
     require!(quantity > 0, ExchangeError::InvalidQuantity);
 
-    let order_book = &ctx.accounts.order_book;
-
-    // Market orders execute immediately against the best available price
-    // In a real implementation, this would match against existing orders
-    // For simplicity, we'll use the last_price or fail if no liquidity
+    let now = Clock::get()?.unix_timestamp;
+    require!(
+        ctx.accounts.kyc_entry.is_valid(now),
+        ExchangeError::KycRequired
+    );
+    require!(
+        ctx.accounts.trading_calendar.phase(now).allows_matching(),
+        ExchangeError::MarketClosed
+    );
 
+    let order_book = &mut ctx.accounts.order_book;
+    require!(!order_book.is_halted(now), ExchangeError::MarketHalted);
+    require!(!order_book.in_auction, ExchangeError::AuctionInProgress);
+    let order_id = order_book.next_order_id;
+    order_book.next_order_id = order_id.checked_add(1).ok_or(ExchangeError::Overflow)?;
+
+    // Sweep the opposite side of the book in price-time priority
+    let client_order_id = client_order_id.unwrap_or(0);
+    let taker = Taker {
+        order_id,
+        client_order_id,
+        trader: ctx.accounts.trader.key(),
+        side,
+        limit_price: None,
+        quantity,
+        quote_budget: (side == OrderSide::Bid).then_some(max_quote_amount),
+        self_trade_behavior,
+    };
+    let makers = match side {
+        OrderSide::Bid => &ctx.accounts.asks,
+        OrderSide::Ask => &ctx.accounts.bids,
+    };
+    let outcome = match_taker(
+        order_book,
+        &mut *makers.load_mut()?,
+        &mut *ctx.accounts.event_queue.load_mut()?,
+        &ctx.accounts.exchange,
+        &taker,
+        now,
+    )?;
+
+    // A tripped circuit breaker must persist even if nothing was filled
     require!(
-        order_book.last_price > 0,
+        outcome.base_filled > 0 || outcome.halted,
         ExchangeError::MarketOrderCannotBeFilled
     );
 
-    let price = order_book.last_price;
-    let sol_amount = price.checked_mul(quantity).ok_or(ExchangeError::Overflow)?;
-
+    // A sell must not sweep the bids below the seller's minimum proceeds
     require!(
-        sol_amount <= max_quote_amount,
-        ExchangeError::MaxQuoteAmountExceeded
+        side == OrderSide::Bid
+            || outcome.base_filled == 0
+            || outcome.quote_amount >= min_quote_amount,
+        ExchangeError::MinimumProceedsNotMet
     );
 
-    // Execute trade based on side
-    match side {
-        OrderSide::Bid => {
-            // Buyer pays SOL, receives base tokens
-            system_program::transfer(
-                CpiContext::new(
-                    ctx.accounts.system_program.to_account_info(),
-                    system_program::Transfer {
-                        from: ctx.accounts.trader.to_account_info(),
-                        to: ctx.accounts.sol_vault.to_account_info(),
-                    },
-                ),
-                sol_amount,
-            )?;
-
-            // Transfer base tokens from vault to buyer
-            let seeds = &[
-                ORDER_BOOK_SEED,
-                order_book.base_mint.as_ref(),
-                &[order_book.bump],
-            ];
-            let signer_seeds = &[&seeds[..]];
-
-            let cpi_accounts_base = Transfer {
-                from: ctx.accounts.base_vault.to_account_info(),
-                to: ctx.accounts.trader_base_account.to_account_info(),
-                authority: ctx.accounts.order_book.to_account_info(),
-            };
-            token::transfer(
-                CpiContext::new_with_signer(
-                    ctx.accounts.token_program.to_account_info(),
-                    cpi_accounts_base,
-                    signer_seeds,
-                ),
-                quantity,
-            )?;
-        }
-        OrderSide::Ask => {
-            // Seller pays base tokens, receives SOL
-            let cpi_accounts_base = Transfer {
-                from: ctx.accounts.trader_base_account.to_account_info(),
-                to: ctx.accounts.base_vault.to_account_info(),
-                authority: ctx.accounts.trader.to_account_info(),
-            };
-            token::transfer(
-                CpiContext::new(
-                    ctx.accounts.token_program.to_account_info(),
-                    cpi_accounts_base,
-                ),
-                quantity,
-            )?;
-
-            // Transfer SOL from vault to seller
-            **ctx.accounts.sol_vault.try_borrow_mut_lamports()? = ctx
-                .accounts
-                .sol_vault
-                .lamports()
-                .checked_sub(sol_amount)
-                .ok_or(ExchangeError::InsufficientFunds)?;
-            **ctx.accounts.trader.try_borrow_mut_lamports()? = ctx
-                .accounts
-                .trader
-                .lamports()
-                .checked_add(sol_amount)
-                .ok_or(ExchangeError::Overflow)?;
-        }
-    }
-
-    let trading_account = &mut ctx.accounts.trading_account;
-    trading_account.total_trades = trading_account
-        .total_trades
-        .checked_add(1)
-        .ok_or(ExchangeError::Overflow)?;
-    trading_account.total_volume = trading_account
-        .total_volume
-        .checked_add(quantity)
-        .ok_or(ExchangeError::Overflow)?;
+    // Lock only what was actually filled; settlement pays it to the other side
+    let lock_amount = match side {
+        OrderSide::Bid => outcome.quote_amount,
+        OrderSide::Ask => order_book.base_amount(outcome.base_filled)?,
+    };
+    let open_orders = &mut ctx.accounts.open_orders;
+    open_orders.lock(side, lock_amount)?;
+
+    // Unlock whatever the trader's own cancelled resting orders had locked
+    open_orders.unlock(side.opposite(), outcome.maker_refund)?;
 
     msg!(
-        "Market order executed: Side {:?}, Quantity {}, Price {} SOL",
+        "Market order {} executed: Side {:?}, Filled {} of {}, Cost {} quote",
+        order_id,
         side,
+        outcome.base_filled,
         quantity,
-        price
+        outcome.quote_amount
     );
 
     Ok(())
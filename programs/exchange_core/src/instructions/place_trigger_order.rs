@@ -0,0 +1,157 @@
+use crate::constants::*;
+use crate::error::ExchangeError;
+use crate::state::*;
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct TriggerOrderParams {
+    pub trigger_id: u64,
+    pub side: OrderSide,
+    pub direction: TriggerDirection,
+    pub trigger_price: u64,
+    pub limit_price: Option<u64>, // None activates a market order (SL-M)
+    pub quantity: u64,
+    pub max_quote_amount: u64, // quote locked for market buys
+    pub self_trade_behavior: SelfTradeBehavior,
+    pub client_order_id: Option<u64>,
+}
+
+#[derive(Accounts)]
+#[instruction(params: TriggerOrderParams)]
+pub struct PlaceTriggerOrder<'info> {
+    #[account(
+        seeds = [EXCHANGE_SEED],
+        bump = exchange.bump,
+    )]
+    pub exchange: Account<'info, Exchange>,
+
+    #[account(
+        mut,
+        constraint = order_book.exchange == exchange.key() @ ExchangeError::Unauthorized,
+        constraint = order_book.is_active @ ExchangeError::OrderBookInactive,
+        constraint = !order_book.is_restating() @ ExchangeError::CorporateActionInProgress,
+    )]
+    pub order_book: Account<'info, OrderBook>,
+
+    #[account(
+        init,
+        payer = trader,
+        space = TriggerOrder::LEN,
+        seeds = [
+            TRIGGER_ORDER_SEED,
+            order_book.key().as_ref(),
+            trader.key().as_ref(),
+            params.trigger_id.to_le_bytes().as_ref()
+        ],
+        bump
+    )]
+    pub trigger_order: Account<'info, TriggerOrder>,
+
+    #[account(mut)]
+    pub trader: Signer<'info>,
+
+    #[account(
+        seeds = [KYC_ENTRY_SEED, trader.key().as_ref()],
+        bump = kyc_entry.bump,
+    )]
+    pub kyc_entry: Account<'info, KycEntry>,
+
+    #[account(
+        mut,
+        seeds = [OPEN_ORDERS_SEED, order_book.key().as_ref(), trader.key().as_ref()],
+        bump = open_orders.bump,
+    )]
+    pub open_orders: Account<'info, OpenOrders>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<PlaceTriggerOrder>, params: TriggerOrderParams) -> Result<()> {
+    require!(!ctx.accounts.exchange.paused, ExchangeError::ExchangePaused);
+    let now = Clock::get()?.unix_timestamp;
+    require!(
+        ctx.accounts.kyc_entry.is_valid(now),
+        ExchangeError::KycRequired
+    );
+
+    let order_book = &ctx.accounts.order_book;
+    require!(
+        params.quantity >= order_book.min_order_size,
+        ExchangeError::QuantityBelowMinimum
+    );
+    require!(params.trigger_price > 0, ExchangeError::InvalidPrice);
+    require!(
+        params.trigger_price.is_multiple_of(order_book.tick_size),
+        ExchangeError::PriceNotAlignedToTickSize
+    );
+    if let Some(price) = params.limit_price {
+        require!(price > 0, ExchangeError::InvalidPrice);
+        require!(
+            price.is_multiple_of(order_book.tick_size),
+            ExchangeError::PriceNotAlignedToTickSize
+        );
+    }
+
+    // Lock collateral from the free balance like a resting order would
+    let (order_type, limit_price) = match params.limit_price {
+        Some(price) => (OrderType::Limit, price),
+        None => (OrderType::Market, 0),
+    };
+    let locked_amount = match (params.side, params.limit_price) {
+        (OrderSide::Bid, Some(price)) => order_book.quote_amount(price, params.quantity)?,
+        (OrderSide::Bid, None) => params.max_quote_amount,
+        (OrderSide::Ask, _) => order_book.base_amount(params.quantity)?,
+    };
+    require!(locked_amount > 0, ExchangeError::InsufficientFunds);
+
+    ctx.accounts.open_orders.lock(params.side, locked_amount)?;
+
+    // Prepay the keeper reward into the trigger order account
+    system_program::transfer(
+        CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            system_program::Transfer {
+                from: ctx.accounts.trader.to_account_info(),
+                to: ctx.accounts.trigger_order.to_account_info(),
+            },
+        ),
+        TRIGGER_KEEPER_REWARD,
+    )?;
+
+    let order_book = &mut ctx.accounts.order_book;
+    order_book.trigger_order_count = order_book
+        .trigger_order_count
+        .checked_add(1)
+        .ok_or(ExchangeError::Overflow)?;
+
+    let trigger_order = &mut ctx.accounts.trigger_order;
+    trigger_order.order_book = order_book.key();
+    trigger_order.trader = ctx.accounts.trader.key();
+    trigger_order.trigger_id = params.trigger_id;
+    trigger_order.side = params.side;
+    trigger_order.order_type = order_type;
+    trigger_order.direction = params.direction;
+    trigger_order.trigger_price = params.trigger_price;
+    trigger_order.limit_price = limit_price;
+    trigger_order.quantity = params.quantity;
+    trigger_order.locked_amount = locked_amount;
+    trigger_order.self_trade_behavior = params.self_trade_behavior;
+    trigger_order.client_order_id = params.client_order_id.unwrap_or(0);
+    trigger_order.keeper_reward = TRIGGER_KEEPER_REWARD;
+    trigger_order.created_at = now;
+    trigger_order.share_epoch = order_book.share_epoch;
+    trigger_order.bump = ctx.bumps.trigger_order;
+
+    msg!(
+        "Trigger order {} placed: Side {:?}, Type {:?}, Trigger {:?} {}, Quantity {}",
+        params.trigger_id,
+        params.side,
+        order_type,
+        params.direction,
+        params.trigger_price,
+        params.quantity
+    );
+
+    Ok(())
+}
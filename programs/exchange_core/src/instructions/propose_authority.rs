@@ -0,0 +1,13 @@
+use crate::instructions::update_exchange::UpdateExchange;
+use anchor_lang::prelude::*;
+
+/// Uses the same accounts as `update_exchange`. The current authority stays in
+/// control until `new_authority` accepts; proposing again replaces the pending
+/// authority and `Pubkey::default()` withdraws the proposal.
+pub fn handler(ctx: Context<UpdateExchange>, new_authority: Pubkey) -> Result<()> {
+    ctx.accounts.exchange.pending_authority = new_authority;
+
+    msg!("Exchange authority handover proposed to {}", new_authority);
+
+    Ok(())
+}
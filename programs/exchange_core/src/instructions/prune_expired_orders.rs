@@ -0,0 +1,66 @@
+use crate::constants::*;
+use crate::error::ExchangeError;
+use crate::matching::*;
+use crate::state::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct PruneExpiredOrders<'info> {
+    #[account(
+        mut,
+        constraint = !order_book.is_restating() @ ExchangeError::CorporateActionInProgress,
+        has_one = bids,
+        has_one = asks,
+        has_one = event_queue,
+    )]
+    pub order_book: Account<'info, OrderBook>,
+
+    #[account(mut)]
+    pub bids: AccountLoader<'info, OrderSlab>,
+
+    #[account(mut)]
+    pub asks: AccountLoader<'info, OrderSlab>,
+
+    #[account(mut)]
+    pub event_queue: AccountLoader<'info, EventQueue>,
+
+    /// Anyone can prune expired orders
+    pub cranker: Signer<'info>,
+}
+
+/// Expired orders leave the book as matching removes them: with an expire
+/// event, whose consumption refunds the owner. Pruning stops early once the
+/// event queue is full.
+pub fn handler(ctx: Context<PruneExpiredOrders>, limit: u8) -> Result<()> {
+    require!(limit <= MAX_PRUNE_ORDERS, ExchangeError::Overflow);
+
+    let now = Clock::get()?.unix_timestamp;
+    let order_book = &mut ctx.accounts.order_book;
+    let mut event_queue = ctx.accounts.event_queue.load_mut()?;
+    let mut pruned: u8 = 0;
+
+    for slab_loader in [&ctx.accounts.bids, &ctx.accounts.asks] {
+        let mut slab = slab_loader.load_mut()?;
+        let side = slab.side();
+        let mut index = 0;
+
+        while index < slab.count as usize && pruned < limit && !event_queue.is_full() {
+            if !slab.nodes[index].is_expired(now) {
+                index += 1;
+                continue;
+            }
+
+            let expired = slab.remove(index);
+            expire_order(order_book, &mut event_queue, side, &expired, now)?;
+            pruned += 1;
+        }
+    }
+
+    msg!(
+        "Pruned {} expired orders from order book {}",
+        pruned,
+        order_book.key()
+    );
+
+    Ok(())
+}
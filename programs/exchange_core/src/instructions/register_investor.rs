@@ -0,0 +1,64 @@
+use crate::constants::*;
+use crate::error::ExchangeError;
+use crate::state::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+#[instruction(wallet: Pubkey)]
+pub struct RegisterInvestor<'info> {
+    #[account(
+        seeds = [COMPLIANCE_REGISTRY_SEED],
+        bump = compliance_registry.bump,
+        constraint = compliance_registry.is_officer(&officer.key()) @ ExchangeError::NotComplianceOfficer,
+    )]
+    pub compliance_registry: Account<'info, ComplianceRegistry>,
+
+    #[account(
+        init,
+        payer = officer,
+        space = KycEntry::LEN,
+        seeds = [KYC_ENTRY_SEED, wallet.as_ref()],
+        bump
+    )]
+    pub kyc_entry: Account<'info, KycEntry>,
+
+    #[account(mut)]
+    pub officer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(
+    ctx: Context<RegisterInvestor>,
+    wallet: Pubkey,
+    category: InvestorCategory,
+    jurisdiction: [u8; 2],
+    expires_at: i64,
+) -> Result<()> {
+    require!(
+        KycEntry::is_valid_jurisdiction(&jurisdiction),
+        ExchangeError::InvalidJurisdiction
+    );
+    let now = Clock::get()?.unix_timestamp;
+    require!(expires_at > now, ExchangeError::InvalidKycExpiry);
+
+    let kyc_entry = &mut ctx.accounts.kyc_entry;
+    kyc_entry.wallet = wallet;
+    kyc_entry.status = KycStatus::Verified;
+    kyc_entry.category = category;
+    kyc_entry.jurisdiction = jurisdiction;
+    kyc_entry.expires_at = expires_at;
+    kyc_entry.verified_by = ctx.accounts.officer.key();
+    kyc_entry.updated_at = now;
+    kyc_entry.bump = ctx.bumps.kyc_entry;
+
+    msg!(
+        "Investor {} verified: {:?}, jurisdiction {}, expires {}",
+        wallet,
+        category,
+        String::from_utf8_lossy(&jurisdiction),
+        expires_at
+    );
+
+    Ok(())
+}
@@ -0,0 +1,16 @@
+use crate::instructions::update_investor::UpdateInvestor;
+use crate::state::*;
+use anchor_lang::prelude::*;
+
+/// Uses the same accounts as `update_investor`. Lifts a suspension; the entry
+/// must still be unexpired for the wallet to trade.
+pub fn handler(ctx: Context<UpdateInvestor>) -> Result<()> {
+    let kyc_entry = &mut ctx.accounts.kyc_entry;
+    kyc_entry.status = KycStatus::Verified;
+    kyc_entry.verified_by = ctx.accounts.officer.key();
+    kyc_entry.updated_at = Clock::get()?.unix_timestamp;
+
+    msg!("Investor {} reinstated", kyc_entry.wallet);
+
+    Ok(())
+}
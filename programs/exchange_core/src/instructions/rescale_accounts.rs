@@ -0,0 +1,72 @@
+use crate::error::ExchangeError;
+use crate::state::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct RescaleAccounts<'info> {
+    #[account(
+        mut,
+        constraint = order_book.is_restating() @ ExchangeError::InvalidCorporateAction,
+    )]
+    pub order_book: Account<'info, OrderBook>,
+
+    /// Anyone can restate accounts after a corporate action
+    pub cranker: Signer<'info>,
+}
+
+/// Remaining accounts are the market's open orders and trigger order accounts
+/// to restate; accounts already in the current share epoch are skipped.
+/// Trading resumes once every account has been restated.
+pub fn handler<'info>(ctx: Context<'_, '_, 'info, 'info, RescaleAccounts<'info>>) -> Result<()> {
+    let order_book = &mut ctx.accounts.order_book;
+    let order_book_key = order_book.key();
+    let multiplier = order_book.share_multiplier;
+    let mut rescaled: u32 = 0;
+
+    for account in ctx.remaining_accounts {
+        if let Ok(mut open_orders) = Account::<OpenOrders>::try_from(account) {
+            require_keys_eq!(
+                open_orders.order_book,
+                order_book_key,
+                ExchangeError::Unauthorized
+            );
+            if open_orders.share_epoch == order_book.share_epoch {
+                continue;
+            }
+
+            open_orders.rescale(multiplier, order_book.share_epoch)?;
+            open_orders.exit(&crate::ID)?;
+        } else {
+            let mut trigger_order = Account::<TriggerOrder>::try_from(account)?;
+            require_keys_eq!(
+                trigger_order.order_book,
+                order_book_key,
+                ExchangeError::Unauthorized
+            );
+            if trigger_order.share_epoch == order_book.share_epoch {
+                continue;
+            }
+
+            trigger_order.rescale(multiplier, order_book.share_epoch)?;
+            trigger_order.exit(&crate::ID)?;
+        }
+
+        order_book.pending_rescales = order_book
+            .pending_rescales
+            .checked_sub(1)
+            .ok_or(ExchangeError::Overflow)?;
+        rescaled += 1;
+    }
+
+    msg!(
+        "Rescaled {} accounts on order book {}, {} remaining",
+        rescaled,
+        order_book_key,
+        order_book.pending_rescales
+    );
+    if !order_book.is_restating() {
+        msg!("Corporate action complete, trading resumes");
+    }
+
+    Ok(())
+}
@@ -0,0 +1,35 @@
+use crate::constants::*;
+use crate::error::ExchangeError;
+use crate::state::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct SetComplianceOfficers<'info> {
+    #[account(
+        seeds = [EXCHANGE_SEED],
+        bump = exchange.bump,
+        has_one = authority @ ExchangeError::Unauthorized,
+    )]
+    pub exchange: Account<'info, Exchange>,
+
+    #[account(
+        mut,
+        seeds = [COMPLIANCE_REGISTRY_SEED],
+        bump = compliance_registry.bump,
+    )]
+    pub compliance_registry: Account<'info, ComplianceRegistry>,
+
+    pub authority: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<SetComplianceOfficers>, officers: Vec<Pubkey>) -> Result<()> {
+    require!(
+        officers.len() <= MAX_COMPLIANCE_OFFICERS,
+        ExchangeError::TooManyComplianceOfficers
+    );
+
+    msg!("Compliance officers updated: {} officers", officers.len());
+    ctx.accounts.compliance_registry.officers = officers;
+
+    Ok(())
+}
@@ -0,0 +1,12 @@
+use crate::instructions::update_exchange::UpdateExchange;
+use anchor_lang::prelude::*;
+
+/// Uses the same accounts as `update_exchange`. While paused, no orders can be
+/// placed, matched or triggered and no new markets listed.
+pub fn handler(ctx: Context<UpdateExchange>, paused: bool) -> Result<()> {
+    ctx.accounts.exchange.paused = paused;
+
+    msg!("Exchange {}", if paused { "paused" } else { "unpaused" });
+
+    Ok(())
+}
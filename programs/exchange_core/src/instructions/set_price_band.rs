@@ -0,0 +1,49 @@
+use crate::constants::*;
+use crate::error::ExchangeError;
+use crate::state::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct SetPriceBand<'info> {
+    #[account(
+        seeds = [EXCHANGE_SEED],
+        bump = exchange.bump,
+        has_one = authority @ ExchangeError::Unauthorized,
+    )]
+    pub exchange: Account<'info, Exchange>,
+
+    #[account(
+        mut,
+        constraint = order_book.exchange == exchange.key() @ ExchangeError::Unauthorized,
+    )]
+    pub order_book: Account<'info, OrderBook>,
+
+    pub authority: Signer<'info>,
+}
+
+/// Called at each session open. Setting a new reference price also lifts any
+/// circuit breaker halt; a reference price of 0 removes the band.
+pub fn handler(
+    ctx: Context<SetPriceBand>,
+    reference_price: u64,
+    price_band_bps: u16,
+) -> Result<()> {
+    require!(
+        price_band_bps as u64 <= BPS_DENOMINATOR,
+        ExchangeError::InvalidPriceBand
+    );
+
+    let order_book = &mut ctx.accounts.order_book;
+    order_book.reference_price = reference_price;
+    order_book.price_band_bps = price_band_bps;
+    order_book.halted_until = 0;
+
+    msg!(
+        "Price band for order book {} set to {} bps around {}",
+        order_book.key(),
+        price_band_bps,
+        reference_price
+    );
+
+    Ok(())
+}
@@ -0,0 +1,11 @@
+use crate::instructions::withdraw::{self, Withdraw};
+use anchor_lang::prelude::*;
+
+/// Uses the same accounts as `withdraw` and sends every free balance back to
+/// the owner's wallet.
+pub fn handler<'info>(ctx: Context<'_, '_, 'info, 'info, Withdraw<'info>>) -> Result<()> {
+    let base_amount = ctx.accounts.open_orders.base_free;
+    let quote_amount = ctx.accounts.open_orders.quote_free;
+
+    withdraw::handler(ctx, base_amount, quote_amount)
+}
@@ -1,47 +1,185 @@
 use crate::constants::*;
 use crate::error::ExchangeError;
 use crate::state::*;
+use crate::vault::*;
 use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 
 #[derive(Accounts)]
 #[instruction(trade_id: u64)]
 pub struct SettleTrade<'info> {
+    #[account(
+        seeds = [EXCHANGE_SEED],
+        bump = exchange.bump,
+    )]
+    pub exchange: Account<'info, Exchange>,
+
     #[account(
         mut,
+        close = rent_payer,
         seeds = [TRADE_SEED, order_book.key().as_ref(), trade_id.to_le_bytes().as_ref()],
         bump,
-        constraint = !trade.settled @ ExchangeError::TradeAlreadySettled,
+        has_one = rent_payer,
     )]
     pub trade: Account<'info, Trade>,
 
+    /// CHECK: Cranker who paid for the trade record, receives its rent
     #[account(mut)]
+    pub rent_payer: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        constraint = order_book.exchange == exchange.key() @ ExchangeError::Unauthorized,
+    )]
     pub order_book: Account<'info, OrderBook>,
 
+    #[account(
+        mut,
+        address = order_book.base_vault,
+    )]
+    pub base_vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        address = order_book.quote_vault,
+    )]
+    pub quote_vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(address = order_book.base_mint)]
+    pub base_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(address = order_book.quote_mint)]
+    pub quote_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        seeds = [OPEN_ORDERS_SEED, order_book.key().as_ref(), trade.buyer().as_ref()],
+        bump = buyer_open_orders.bump,
+    )]
+    pub buyer_open_orders: Account<'info, OpenOrders>,
+
+    #[account(
+        mut,
+        seeds = [OPEN_ORDERS_SEED, order_book.key().as_ref(), trade.seller().as_ref()],
+        bump = seller_open_orders.bump,
+    )]
+    pub seller_open_orders: Account<'info, OpenOrders>,
+
+    #[account(
+        mut,
+        seeds = [TRADING_ACCOUNT_SEED, trade.buyer().as_ref()],
+        bump = buyer_trading_account.bump,
+    )]
+    pub buyer_trading_account: Account<'info, TradingAccount>,
+
+    #[account(
+        mut,
+        seeds = [TRADING_ACCOUNT_SEED, trade.seller().as_ref()],
+        bump = seller_trading_account.bump,
+    )]
+    pub seller_trading_account: Account<'info, TradingAccount>,
+
+    #[account(
+        mut,
+        constraint = fee_collector_base_account.owner == exchange.fee_collector @ ExchangeError::Unauthorized,
+        constraint = fee_collector_base_account.mint == order_book.base_mint @ ExchangeError::InvalidMint,
+    )]
+    pub fee_collector_base_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = fee_collector_quote_account.owner == exchange.fee_collector @ ExchangeError::Unauthorized,
+        constraint = fee_collector_quote_account.mint == order_book.quote_mint @ ExchangeError::InvalidMint,
+    )]
+    pub fee_collector_quote_account: InterfaceAccount<'info, TokenAccount>,
+
     /// CHECK: Trade settler
     pub settler: Signer<'info>,
+
+    pub base_token_program: Interface<'info, TokenInterface>,
+    pub quote_token_program: Interface<'info, TokenInterface>,
 }
 
-pub fn handler(ctx: Context<SettleTrade>, trade_id: u64) -> Result<()> {
-    let trade = &mut ctx.accounts.trade;
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, SettleTrade<'info>>,
+    trade_id: u64,
+) -> Result<()> {
+    let trade = &ctx.accounts.trade;
+    let base_amount = ctx.accounts.order_book.base_amount(trade.quantity)?;
+    let notional = ctx
+        .accounts
+        .order_book
+        .quote_amount(trade.price, trade.quantity)?;
+    let (buyer_fee, seller_fee) = trade.buyer_and_seller_fees();
+
+    // The buyer's locked quote pays the seller and the seller's locked base tokens
+    // pay the buyer, each less their fee; quote the buyer locked above the trade
+    // price is unlocked again
+    let buyer = &mut ctx.accounts.buyer_open_orders;
+    buyer.spend_locked(OrderSide::Bid, notional)?;
+    buyer.unlock(OrderSide::Bid, trade.price_improvement)?;
+    buyer.credit_base(
+        base_amount
+            .checked_sub(buyer_fee)
+            .ok_or(ExchangeError::Overflow)?,
+    )?;
+
+    let seller = &mut ctx.accounts.seller_open_orders;
+    seller.spend_locked(OrderSide::Ask, base_amount)?;
+    seller.credit_quote(
+        notional
+            .checked_sub(seller_fee)
+            .ok_or(ExchangeError::Overflow)?,
+    )?;
+
+    // Fees leave the vaults for the fee collector
+    release_tokens(
+        &ctx.accounts.order_book,
+        &ctx.accounts.base_vault,
+        &ctx.accounts.base_mint,
+        ctx.accounts.fee_collector_base_account.to_account_info(),
+        &ctx.accounts.base_token_program,
+        ctx.remaining_accounts,
+        buyer_fee,
+    )?;
+    release_tokens(
+        &ctx.accounts.order_book,
+        &ctx.accounts.quote_vault,
+        &ctx.accounts.quote_mint,
+        ctx.accounts.fee_collector_quote_account.to_account_info(),
+        &ctx.accounts.quote_token_program,
+        ctx.remaining_accounts,
+        seller_fee,
+    )?;
 
-    // Mark trade as settled
-    trade.settled = true;
+    for trading_account in [
+        &mut ctx.accounts.buyer_trading_account,
+        &mut ctx.accounts.seller_trading_account,
+    ] {
+        trading_account.total_trades = trading_account
+            .total_trades
+            .checked_add(1)
+            .ok_or(ExchangeError::Overflow)?;
+        trading_account.total_volume = trading_account
+            .total_volume
+            .checked_add(trade.quantity)
+            .ok_or(ExchangeError::Overflow)?;
+    }
 
-    // Update order book statistics
+    let trade = &ctx.accounts.trade;
     let order_book = &mut ctx.accounts.order_book;
-    order_book.total_volume = order_book
-        .total_volume
-        .checked_add(trade.quantity)
+    order_book.unsettled_trades = order_book
+        .unsettled_trades
+        .checked_sub(1)
         .ok_or(ExchangeError::Overflow)?;
-    order_book.last_price = trade.price;
 
     msg!(
-        "Trade {} settled: Price {}, Quantity {}, Maker fee: {}, Taker fee: {}",
+        "Trade {} settled: Price {}, Quantity {}, Buyer fee: {} base, Seller fee: {} quote",
         trade_id,
         trade.price,
         trade.quantity,
-        trade.maker_fee,
-        trade.taker_fee
+        buyer_fee,
+        seller_fee
     );
 
     Ok(())
@@ -0,0 +1,170 @@
+use crate::constants::*;
+use crate::error::ExchangeError;
+use crate::matching::*;
+use crate::state::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct UncrossAuction<'info> {
+    #[account(
+        seeds = [EXCHANGE_SEED],
+        bump = exchange.bump,
+    )]
+    pub exchange: Account<'info, Exchange>,
+
+    #[account(
+        seeds = [TRADING_CALENDAR_SEED],
+        bump = trading_calendar.bump,
+    )]
+    pub trading_calendar: Account<'info, TradingCalendar>,
+
+    #[account(
+        mut,
+        constraint = order_book.exchange == exchange.key() @ ExchangeError::Unauthorized,
+        constraint = order_book.is_active @ ExchangeError::OrderBookInactive,
+        constraint = !order_book.is_restating() @ ExchangeError::CorporateActionInProgress,
+        has_one = bids,
+        has_one = asks,
+        has_one = event_queue,
+    )]
+    pub order_book: Account<'info, OrderBook>,
+
+    #[account(mut)]
+    pub bids: AccountLoader<'info, OrderSlab>,
+
+    #[account(mut)]
+    pub asks: AccountLoader<'info, OrderSlab>,
+
+    #[account(mut)]
+    pub event_queue: AccountLoader<'info, EventQueue>,
+
+    /// Anyone can uncross the auction once the session opens
+    pub cranker: Signer<'info>,
+}
+
+/// The first call fixes the uncrossing price; if the event queue fills up
+/// before every crossed order is filled, later calls carry on at that price.
+/// The auction ends once nothing crosses it, seeding `last_price`.
+pub fn handler(ctx: Context<UncrossAuction>, max_fills: u8) -> Result<()> {
+    require!(max_fills <= MAX_CRANK_ITERATIONS, ExchangeError::Overflow);
+    require!(!ctx.accounts.exchange.paused, ExchangeError::ExchangePaused);
+
+    let timestamp = Clock::get()?.unix_timestamp;
+    require!(
+        ctx.accounts
+            .trading_calendar
+            .phase(timestamp)
+            .allows_matching(),
+        ExchangeError::MarketClosed
+    );
+
+    let exchange = &ctx.accounts.exchange;
+    let order_book = &mut ctx.accounts.order_book;
+    require!(order_book.in_auction, ExchangeError::NoAuctionInProgress);
+    require!(
+        !order_book.is_halted(timestamp),
+        ExchangeError::MarketHalted
+    );
+    let mut bids = ctx.accounts.bids.load_mut()?;
+    let mut asks = ctx.accounts.asks.load_mut()?;
+    let mut event_queue = ctx.accounts.event_queue.load_mut()?;
+
+    if order_book.auction_price == 0 {
+        let Some(price) = auction_price(order_book, &bids, &asks, timestamp) else {
+            order_book.in_auction = false;
+            msg!(
+                "Call auction closed for order book {} without crossing orders",
+                order_book.key()
+            );
+            return Ok(());
+        };
+        order_book.auction_price = price;
+        msg!("Call auction uncrossing at price {}", price);
+    }
+
+    let price = order_book.auction_price;
+    let mut fills: u8 = 0;
+    let mut uncrossed = false;
+
+    while fills < max_fills && !event_queue.is_full() {
+        let (Some(bid), Some(ask)) = (bids.best().copied(), asks.best().copied()) else {
+            uncrossed = true;
+            break;
+        };
+        if bid.is_expired(timestamp) {
+            expire_best(&mut bids, order_book, &mut event_queue, timestamp)?;
+            continue;
+        }
+        if ask.is_expired(timestamp) {
+            expire_best(&mut asks, order_book, &mut event_queue, timestamp)?;
+            continue;
+        }
+        if bid.price < price || ask.price > price {
+            uncrossed = true;
+            break;
+        }
+        // As in the crank, the newer of a trader's crossed orders is cancelled
+        if bid.trader == ask.trader {
+            msg!(
+                "Self-trade prevented: bid {} and ask {} belong to the same trader",
+                bid.order_id,
+                ask.order_id
+            );
+            if bid.order_id < ask.order_id {
+                evict_best(&mut asks, order_book, &mut event_queue, timestamp)?;
+            } else {
+                evict_best(&mut bids, order_book, &mut event_queue, timestamp)?;
+            }
+            continue;
+        }
+
+        // Every fill executes at the auction price; the older order is the maker
+        let quantity = bid.remaining_quantity().min(ask.remaining_quantity());
+        let (maker, taker) = if bid.order_id < ask.order_id {
+            (bid, Taker::from_resting(&ask, OrderSide::Ask))
+        } else {
+            (ask, Taker::from_resting(&bid, OrderSide::Bid))
+        };
+        let maker = OrderNode { price, ..maker };
+
+        // Bids locked quote at their own price; the excess goes back on settlement
+        let price_improvement = order_book.quote_amount(bid.price, quantity)?
+            - order_book.quote_amount(price, quantity)?;
+
+        record_fill(
+            order_book,
+            &mut event_queue,
+            exchange,
+            &maker,
+            &taker,
+            quantity,
+            price_improvement,
+            timestamp,
+        )?;
+        fill_best(&mut bids, order_book, quantity)?;
+        fill_best(&mut asks, order_book, quantity)?;
+
+        fills += 1;
+    }
+
+    if uncrossed {
+        order_book.in_auction = false;
+        order_book.auction_price = 0;
+        order_book.last_price = price;
+        msg!(
+            "Call auction closed for order book {} at price {} ({} fills)",
+            order_book.key(),
+            price,
+            fills
+        );
+    } else {
+        msg!(
+            "Call auction partially uncrossed for order book {} at price {} ({} fills)",
+            order_book.key(),
+            price,
+            fills
+        );
+    }
+
+    Ok(())
+}
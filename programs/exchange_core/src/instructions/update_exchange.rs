@@ -0,0 +1,57 @@
+use crate::constants::*;
+use crate::error::ExchangeError;
+use crate::state::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct UpdateExchange<'info> {
+    #[account(
+        mut,
+        seeds = [EXCHANGE_SEED],
+        bump = exchange.bump,
+        has_one = authority @ ExchangeError::Unauthorized,
+    )]
+    pub exchange: Account<'info, Exchange>,
+
+    pub authority: Signer<'info>,
+}
+
+pub fn handler(
+    ctx: Context<UpdateExchange>,
+    maker_fee_bps: Option<u16>,
+    taker_fee_bps: Option<u16>,
+    fee_collector: Option<Pubkey>,
+    listing_program: Option<Pubkey>,
+) -> Result<()> {
+    let exchange = &mut ctx.accounts.exchange;
+
+    if let Some(maker_fee_bps) = maker_fee_bps {
+        require!(
+            maker_fee_bps <= MAX_FEE_BPS,
+            ExchangeError::InvalidFeePercentage
+        );
+        exchange.maker_fee_bps = maker_fee_bps;
+        msg!("Maker fee updated to {} bps", maker_fee_bps);
+    }
+
+    if let Some(taker_fee_bps) = taker_fee_bps {
+        require!(
+            taker_fee_bps <= MAX_FEE_BPS,
+            ExchangeError::InvalidFeePercentage
+        );
+        exchange.taker_fee_bps = taker_fee_bps;
+        msg!("Taker fee updated to {} bps", taker_fee_bps);
+    }
+
+    if let Some(fee_collector) = fee_collector {
+        exchange.fee_collector = fee_collector;
+        msg!("Fee collector updated to {}", fee_collector);
+    }
+
+    if let Some(listing_program) = listing_program {
+        exchange.listing_program = listing_program;
+        msg!("Listing program updated to {}", listing_program);
+    }
+
+    Ok(())
+}
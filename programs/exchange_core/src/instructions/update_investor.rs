@@ -0,0 +1,62 @@
+use crate::constants::*;
+use crate::error::ExchangeError;
+use crate::state::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct UpdateInvestor<'info> {
+    #[account(
+        seeds = [COMPLIANCE_REGISTRY_SEED],
+        bump = compliance_registry.bump,
+        constraint = compliance_registry.is_officer(&officer.key()) @ ExchangeError::NotComplianceOfficer,
+    )]
+    pub compliance_registry: Account<'info, ComplianceRegistry>,
+
+    #[account(
+        mut,
+        seeds = [KYC_ENTRY_SEED, kyc_entry.wallet.as_ref()],
+        bump = kyc_entry.bump,
+        constraint = kyc_entry.status != KycStatus::Revoked @ ExchangeError::KycRevoked,
+    )]
+    pub kyc_entry: Account<'info, KycEntry>,
+
+    pub officer: Signer<'info>,
+}
+
+pub fn handler(
+    ctx: Context<UpdateInvestor>,
+    category: Option<InvestorCategory>,
+    jurisdiction: Option<[u8; 2]>,
+    expires_at: Option<i64>,
+) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let kyc_entry = &mut ctx.accounts.kyc_entry;
+
+    if let Some(category) = category {
+        kyc_entry.category = category;
+        msg!("Investor category updated: {:?}", category);
+    }
+
+    if let Some(jurisdiction) = jurisdiction {
+        require!(
+            KycEntry::is_valid_jurisdiction(&jurisdiction),
+            ExchangeError::InvalidJurisdiction
+        );
+        kyc_entry.jurisdiction = jurisdiction;
+        msg!(
+            "Investor jurisdiction updated: {}",
+            String::from_utf8_lossy(&jurisdiction)
+        );
+    }
+
+    if let Some(expires_at) = expires_at {
+        require!(expires_at > now, ExchangeError::InvalidKycExpiry);
+        kyc_entry.expires_at = expires_at;
+        msg!("KYC expiry updated: {}", expires_at);
+    }
+
+    kyc_entry.verified_by = ctx.accounts.officer.key();
+    kyc_entry.updated_at = now;
+
+    Ok(())
+}
@@ -0,0 +1,48 @@
+use crate::constants::*;
+use crate::error::ExchangeError;
+use crate::state::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct UpdateTradingCalendar<'info> {
+    #[account(
+        seeds = [EXCHANGE_SEED],
+        bump = exchange.bump,
+        has_one = authority @ ExchangeError::Unauthorized,
+    )]
+    pub exchange: Account<'info, Exchange>,
+
+    #[account(
+        mut,
+        seeds = [TRADING_CALENDAR_SEED],
+        bump = trading_calendar.bump,
+    )]
+    pub trading_calendar: Account<'info, TradingCalendar>,
+
+    pub authority: Signer<'info>,
+}
+
+pub fn handler(
+    ctx: Context<UpdateTradingCalendar>,
+    schedule: Option<SessionSchedule>,
+    holidays: Option<Vec<i64>>,
+) -> Result<()> {
+    let trading_calendar = &mut ctx.accounts.trading_calendar;
+
+    if let Some(schedule) = schedule {
+        require!(schedule.is_valid(), ExchangeError::InvalidSessionSchedule);
+        trading_calendar.schedule = schedule;
+        msg!("Session schedule updated: {:?}", schedule);
+    }
+
+    if let Some(holidays) = holidays {
+        require!(
+            holidays.len() <= MAX_HOLIDAYS,
+            ExchangeError::TooManyHolidays
+        );
+        msg!("Holiday list updated: {} holidays", holidays.len());
+        trading_calendar.holidays = holidays;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,104 @@
+use crate::constants::*;
+use crate::error::ExchangeError;
+use crate::state::*;
+use crate::vault::*;
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+#[derive(Accounts)]
+pub struct Withdraw<'info> {
+    #[account(
+        constraint = !order_book.is_restating() @ ExchangeError::CorporateActionInProgress,
+    )]
+    pub order_book: Account<'info, OrderBook>,
+
+    #[account(
+        mut,
+        seeds = [OPEN_ORDERS_SEED, order_book.key().as_ref(), owner.key().as_ref()],
+        bump = open_orders.bump,
+    )]
+    pub open_orders: Account<'info, OpenOrders>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = owner_base_account.owner == owner.key() @ ExchangeError::Unauthorized,
+        constraint = owner_base_account.mint == order_book.base_mint @ ExchangeError::InvalidMint,
+    )]
+    pub owner_base_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        address = order_book.base_vault,
+    )]
+    pub base_vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = owner_quote_account.owner == owner.key() @ ExchangeError::Unauthorized,
+        constraint = owner_quote_account.mint == order_book.quote_mint @ ExchangeError::InvalidMint,
+    )]
+    pub owner_quote_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        address = order_book.quote_vault,
+    )]
+    pub quote_vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(address = order_book.base_mint)]
+    pub base_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(address = order_book.quote_mint)]
+    pub quote_mint: InterfaceAccount<'info, Mint>,
+
+    pub base_token_program: Interface<'info, TokenInterface>,
+    pub quote_token_program: Interface<'info, TokenInterface>,
+}
+
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, Withdraw<'info>>,
+    base_amount: u64,
+    quote_amount: u64,
+) -> Result<()> {
+    // Only free balances can leave; locked funds back orders and unsettled trades
+    let open_orders = &mut ctx.accounts.open_orders;
+    open_orders.base_free = open_orders
+        .base_free
+        .checked_sub(base_amount)
+        .ok_or(ExchangeError::InsufficientFunds)?;
+    open_orders.quote_free = open_orders
+        .quote_free
+        .checked_sub(quote_amount)
+        .ok_or(ExchangeError::InsufficientFunds)?;
+
+    release_tokens(
+        &ctx.accounts.order_book,
+        &ctx.accounts.base_vault,
+        &ctx.accounts.base_mint,
+        ctx.accounts.owner_base_account.to_account_info(),
+        &ctx.accounts.base_token_program,
+        ctx.remaining_accounts,
+        base_amount,
+    )?;
+    release_tokens(
+        &ctx.accounts.order_book,
+        &ctx.accounts.quote_vault,
+        &ctx.accounts.quote_mint,
+        ctx.accounts.owner_quote_account.to_account_info(),
+        &ctx.accounts.quote_token_program,
+        ctx.remaining_accounts,
+        quote_amount,
+    )?;
+
+    msg!(
+        "Withdrew {} base and {} quote from order book {}",
+        base_amount,
+        quote_amount,
+        ctx.accounts.order_book.key()
+    );
+
+    Ok(())
+}
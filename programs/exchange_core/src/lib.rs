@@ -1,11 +1,13 @@
 pub mod constants;
 pub mod error;
 pub mod instructions;
+pub mod matching;
 pub mod state;
+pub mod vault;
 
 use anchor_lang::prelude::*;
 use instructions::*;
-use state::{OrderSide, OrderType};
+use state::{InvestorCategory, MarketParams, OrderSide, SelfTradeBehavior, SessionSchedule};
 
 declare_id!("ExU8EoUrjN9xRi9n8af1i83fhALqTMCt5qjrdqMdG9RD");
 
@@ -22,39 +24,71 @@ pub mod exchange_core {
         instructions::initialize_exchange::handler(ctx, maker_fee_bps, taker_fee_bps)
     }
 
-    /// Initialize an order book for a trading pair (base token vs SOL)
+    /// Initialize an order book for a trading pair (base token vs quote token).
+    /// SOL markets use wrapped SOL as the quote mint.
+    /// Quantities are counted in base lots and prices in quote lots per base lot
+    /// with `PRICE_DECIMALS` decimals.
+    /// Signed by the exchange authority, or by the listing authority when an
+    /// offering lists; a non-zero `reference_price` opens the market with its price band.
     pub fn initialize_order_book(
         ctx: Context<InitializeOrderBook>,
         base_mint: Pubkey,
-        tick_size: u64,
-        min_order_size: u64,
+        params: MarketParams,
+        reference_price: u64,
     ) -> Result<()> {
-        instructions::initialize_order_book::handler(
-            ctx,
-            base_mint,
-            tick_size,
-            min_order_size,
-        )
+        instructions::initialize_order_book::handler(ctx, base_mint, params, reference_price)
     }
 
-    /// Place a limit order
+    /// Place a limit order (Limit, PostOnly, ImmediateOrCancel or FillOrKill).
+    /// `self_trade_behavior` decides what happens when it meets the trader's own resting orders.
+    /// `client_order_id` is stored on the order and echoed in its events.
+    /// A resting order with `expires_at` is removed from the book once that time passes.
     pub fn place_limit_order(
         ctx: Context<PlaceLimitOrder>,
-        side: OrderSide,
-        price: u64,
-        quantity: u64,
+        params: LimitOrderParams,
     ) -> Result<()> {
-        instructions::place_limit_order::handler(ctx, side, price, quantity)
+        instructions::place_limit_order::handler(ctx, params)
     }
 
-    /// Place a market order
+    /// Place a market order that sweeps the opposite side of the book.
+    /// `max_quote_amount` caps the quote a buy may spend and `min_quote_amount`
+    /// is the least a sell must receive; any unfilled remainder is cancelled.
     pub fn place_market_order(
         ctx: Context<PlaceMarketOrder>,
         side: OrderSide,
         quantity: u64,
         max_quote_amount: u64,
+        min_quote_amount: u64,
+        self_trade_behavior: SelfTradeBehavior,
+        client_order_id: Option<u64>,
     ) -> Result<()> {
-        instructions::place_market_order::handler(ctx, side, quantity, max_quote_amount)
+        instructions::place_market_order::handler(
+            ctx,
+            side,
+            quantity,
+            max_quote_amount,
+            min_quote_amount,
+            self_trade_behavior,
+            client_order_id,
+        )
+    }
+
+    /// Rest a stop-loss or take-profit order that activates once the last price crosses its trigger
+    pub fn place_trigger_order(
+        ctx: Context<PlaceTriggerOrder>,
+        params: TriggerOrderParams,
+    ) -> Result<()> {
+        instructions::place_trigger_order::handler(ctx, params)
+    }
+
+    /// Activate a triggered order into a limit or market order (permissionless, pays a keeper reward)
+    pub fn activate_trigger_order(ctx: Context<ActivateTriggerOrder>) -> Result<()> {
+        instructions::activate_trigger_order::handler(ctx)
+    }
+
+    /// Cancel a pending trigger order and return its collateral
+    pub fn cancel_trigger_order(ctx: Context<CancelTriggerOrder>) -> Result<()> {
+        instructions::cancel_trigger_order::handler(ctx)
     }
 
     /// Cancel an existing order
@@ -62,7 +96,21 @@ pub mod exchange_core {
         instructions::cancel_order::handler(ctx, order_id)
     }
 
-    /// Modify an existing order
+    /// Cancel the caller's resting order by the client order ID it was placed with
+    pub fn cancel_order_by_client_id(
+        ctx: Context<CancelOrder>,
+        client_order_id: u64,
+    ) -> Result<()> {
+        instructions::cancel_order_by_client_id::handler(ctx, client_order_id)
+    }
+
+    /// Cancel every resting order of a wallet whose KYC is no longer valid (permissionless)
+    pub fn cancel_blocked_orders(ctx: Context<CancelBlockedOrders>) -> Result<()> {
+        instructions::cancel_blocked_orders::handler(ctx)
+    }
+
+    /// Modify an existing order, topping up or refunding its locked collateral.
+    /// Reducing the quantity keeps time priority; a price change or increase loses it.
     pub fn modify_order(
         ctx: Context<ModifyOrder>,
         order_id: u64,
@@ -77,17 +125,207 @@ pub mod exchange_core {
         instructions::crank_match_orders::handler(ctx, max_iterations)
     }
 
-    /// Initialize a user trading account
+    /// Drain the event queue, recording fills as trades awaiting settlement
+    /// and refunding orders that expired during matching
+    pub fn consume_events<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ConsumeEvents<'info>>,
+        limit: u16,
+    ) -> Result<()> {
+        instructions::consume_events::handler(ctx, limit)
+    }
+
+    /// Remove expired orders anywhere in the book, queueing their refunds (permissionless)
+    pub fn prune_expired_orders(ctx: Context<PruneExpiredOrders>, limit: u8) -> Result<()> {
+        instructions::prune_expired_orders::handler(ctx, limit)
+    }
+
+    /// Initialize a user trading account; the owner must hold valid, unexpired KYC
     pub fn initialize_trading_account(ctx: Context<InitializeTradingAccount>) -> Result<()> {
         instructions::initialize_trading_account::handler(ctx)
     }
 
-    /// Settle a completed trade
-    pub fn settle_trade(ctx: Context<SettleTrade>, trade_id: u64) -> Result<()> {
+    /// Create the caller's open orders account holding their balances on a market
+    pub fn initialize_open_orders(ctx: Context<InitializeOpenOrders>) -> Result<()> {
+        instructions::initialize_open_orders::handler(ctx)
+    }
+
+    /// Deposit base and/or quote tokens into the caller's free balances
+    pub fn deposit<'info>(
+        ctx: Context<'_, '_, 'info, 'info, Deposit<'info>>,
+        base_amount: u64,
+        quote_amount: u64,
+    ) -> Result<()> {
+        instructions::deposit::handler(ctx, base_amount, quote_amount)
+    }
+
+    /// Withdraw base and/or quote tokens from the caller's free balances
+    pub fn withdraw<'info>(
+        ctx: Context<'_, '_, 'info, 'info, Withdraw<'info>>,
+        base_amount: u64,
+        quote_amount: u64,
+    ) -> Result<()> {
+        instructions::withdraw::handler(ctx, base_amount, quote_amount)
+    }
+
+    /// Withdraw every free balance, including proceeds credited by settled trades
+    pub fn settle_funds<'info>(ctx: Context<'_, '_, 'info, 'info, Withdraw<'info>>) -> Result<()> {
+        instructions::settle_funds::handler(ctx)
+    }
+
+    /// Settle a completed trade: credit both sides' free balances and collect fees
+    pub fn settle_trade<'info>(
+        ctx: Context<'_, '_, 'info, 'info, SettleTrade<'info>>,
+        trade_id: u64,
+    ) -> Result<()> {
         instructions::settle_trade::handler(ctx, trade_id)
     }
 
-    /// Close an order book (admin only)
+    /// Update the trading fees, fee collector and/or listing program (admin only)
+    pub fn update_exchange(
+        ctx: Context<UpdateExchange>,
+        maker_fee_bps: Option<u16>,
+        taker_fee_bps: Option<u16>,
+        fee_collector: Option<Pubkey>,
+        listing_program: Option<Pubkey>,
+    ) -> Result<()> {
+        instructions::update_exchange::handler(
+            ctx,
+            maker_fee_bps,
+            taker_fee_bps,
+            fee_collector,
+            listing_program,
+        )
+    }
+
+    /// Halt or resume all trading on the exchange (admin only)
+    pub fn set_paused(ctx: Context<UpdateExchange>, paused: bool) -> Result<()> {
+        instructions::set_paused::handler(ctx, paused)
+    }
+
+    /// Propose a new exchange authority, who must accept before taking over (admin only)
+    pub fn propose_authority(ctx: Context<UpdateExchange>, new_authority: Pubkey) -> Result<()> {
+        instructions::propose_authority::handler(ctx, new_authority)
+    }
+
+    /// Accept a proposed authority handover, signed by the proposed authority
+    pub fn accept_authority(ctx: Context<AcceptAuthority>) -> Result<()> {
+        instructions::accept_authority::handler(ctx)
+    }
+
+    /// Reset a market's reference price and band at session open, lifting any
+    /// circuit breaker halt (admin only)
+    pub fn set_price_band(
+        ctx: Context<SetPriceBand>,
+        reference_price: u64,
+        price_band_bps: u16,
+    ) -> Result<()> {
+        instructions::set_price_band::handler(ctx, reference_price, price_band_bps)
+    }
+
+    /// Create the exchange's trading calendar: daily session times in local
+    /// time and the holidays with no trading (admin only)
+    pub fn initialize_trading_calendar(
+        ctx: Context<InitializeTradingCalendar>,
+        schedule: SessionSchedule,
+        holidays: Vec<i64>,
+    ) -> Result<()> {
+        instructions::initialize_trading_calendar::handler(ctx, schedule, holidays)
+    }
+
+    /// Replace the session schedule and/or holiday list (admin only)
+    pub fn update_trading_calendar(
+        ctx: Context<UpdateTradingCalendar>,
+        schedule: Option<SessionSchedule>,
+        holidays: Option<Vec<i64>>,
+    ) -> Result<()> {
+        instructions::update_trading_calendar::handler(ctx, schedule, holidays)
+    }
+
+    /// Put a market into a call auction: orders accumulate without matching
+    /// until the auction is uncrossed (admin only)
+    pub fn open_auction(ctx: Context<OpenAuction>) -> Result<()> {
+        instructions::open_auction::handler(ctx)
+    }
+
+    /// Fill every crossed order at the auction's equilibrium price once the
+    /// session is open, seeding the last price (permissionless)
+    pub fn uncross_auction(ctx: Context<UncrossAuction>, max_fills: u8) -> Result<()> {
+        instructions::uncross_auction::handler(ctx, max_fills)
+    }
+
+    /// Restate a market for a stock split or bonus issue, minting the new shares
+    /// into its base vault and halting it until every account is rescaled
+    /// (admin and base mint authority)
+    pub fn apply_corporate_action(
+        ctx: Context<ApplyCorporateAction>,
+        share_multiplier: u64,
+    ) -> Result<()> {
+        instructions::apply_corporate_action::handler(ctx, share_multiplier)
+    }
+
+    /// Restate open orders and trigger order accounts after a corporate action (permissionless)
+    pub fn rescale_accounts<'info>(
+        ctx: Context<'_, '_, 'info, 'info, RescaleAccounts<'info>>,
+    ) -> Result<()> {
+        instructions::rescale_accounts::handler(ctx)
+    }
+
+    /// Create the compliance registry listing the officers who maintain KYC entries (admin only)
+    pub fn initialize_compliance_registry(
+        ctx: Context<InitializeComplianceRegistry>,
+        officers: Vec<Pubkey>,
+    ) -> Result<()> {
+        instructions::initialize_compliance_registry::handler(ctx, officers)
+    }
+
+    /// Replace the list of compliance officers (admin only)
+    pub fn set_compliance_officers(
+        ctx: Context<SetComplianceOfficers>,
+        officers: Vec<Pubkey>,
+    ) -> Result<()> {
+        instructions::set_compliance_officers::handler(ctx, officers)
+    }
+
+    /// Record a wallet's verified KYC, allowing it to trade until `expires_at`
+    /// (compliance officers only)
+    pub fn register_investor(
+        ctx: Context<RegisterInvestor>,
+        wallet: Pubkey,
+        category: InvestorCategory,
+        jurisdiction: [u8; 2],
+        expires_at: i64,
+    ) -> Result<()> {
+        instructions::register_investor::handler(ctx, wallet, category, jurisdiction, expires_at)
+    }
+
+    /// Update a wallet's investor category, jurisdiction and/or KYC expiry
+    /// (compliance officers only)
+    pub fn update_investor(
+        ctx: Context<UpdateInvestor>,
+        category: Option<InvestorCategory>,
+        jurisdiction: Option<[u8; 2]>,
+        expires_at: Option<i64>,
+    ) -> Result<()> {
+        instructions::update_investor::handler(ctx, category, jurisdiction, expires_at)
+    }
+
+    /// Block a wallet from opening a trading account or placing orders until
+    /// reinstated (compliance officers only)
+    pub fn suspend_investor(ctx: Context<UpdateInvestor>) -> Result<()> {
+        instructions::suspend_investor::handler(ctx)
+    }
+
+    /// Lift a wallet's suspension (compliance officers only)
+    pub fn reinstate_investor(ctx: Context<UpdateInvestor>) -> Result<()> {
+        instructions::reinstate_investor::handler(ctx)
+    }
+
+    /// Permanently block a wallet from trading (compliance officers only)
+    pub fn revoke_investor(ctx: Context<UpdateInvestor>) -> Result<()> {
+        instructions::revoke_investor::handler(ctx)
+    }
+
+    /// Close an order book (admin only), returning the rent of its slabs and event queue
     pub fn close_order_book(ctx: Context<CloseOrderBook>) -> Result<()> {
         instructions::close_order_book::handler(ctx)
     }
@@ -0,0 +1,857 @@
+use crate::error::ExchangeError;
+use crate::state::*;
+use anchor_lang::prelude::*;
+use std::cmp::Reverse;
+
+/// Incoming order being matched against resting orders on the other side
+pub struct Taker {
+    pub order_id: u64,
+    pub client_order_id: u64,
+    pub trader: Pubkey,
+    pub side: OrderSide,
+    pub limit_price: Option<u64>, // None matches at any price
+    pub quantity: u64,
+    pub quote_budget: Option<u64>, // most quote a buy may spend
+    pub self_trade_behavior: SelfTradeBehavior,
+}
+
+impl Taker {
+    /// The newer of two crossed resting orders, as seen by the crank
+    pub fn from_resting(node: &OrderNode, side: OrderSide) -> Self {
+        Self {
+            order_id: node.order_id,
+            client_order_id: node.client_order_id,
+            trader: node.trader,
+            side,
+            limit_price: Some(node.price),
+            quantity: node.remaining_quantity(),
+            quote_budget: None,
+            self_trade_behavior: SelfTradeBehavior::CancelTaker,
+        }
+    }
+
+    pub fn crosses(&self, maker_price: u64) -> bool {
+        match self.limit_price {
+            Some(limit) => !self.side.outranks(maker_price, limit),
+            None => true,
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct MatchOutcome {
+    pub base_filled: u64,
+    pub quote_amount: u64,
+    pub self_trade_decrement: u64, // taker quantity dropped by DecrementAndCancel
+    pub maker_refund: u64,         // funds released from the taker's own cancelled makers
+    pub taker_cancelled: bool,     // the remainder must not rest
+    pub halted: bool,              // a fill would have breached the price band
+}
+
+impl MatchOutcome {
+    pub fn taker_remaining(&self, taker: &Taker) -> u64 {
+        taker
+            .quantity
+            .saturating_sub(self.base_filled)
+            .saturating_sub(self.self_trade_decrement)
+    }
+}
+
+/// Fill `taker` against `makers` in price-time priority until it is complete,
+/// the book stops crossing or the quote budget runs out. Fails with
+/// `EventQueueFull` if the queue has no room for the next event, so a
+/// remainder is never left resting on a book it crosses.
+///
+/// A fill outside the market's price band trips its circuit breaker instead,
+/// halting the market and ending the match.
+///
+/// Resting orders from the taker's own account are never traded against; they
+/// are handled according to `taker.self_trade_behavior`, and whatever they had
+/// locked is reported in `maker_refund` for the caller to return.
+pub fn match_taker(
+    order_book: &mut OrderBook,
+    makers: &mut OrderSlab,
+    event_queue: &mut EventQueue,
+    exchange: &Exchange,
+    taker: &Taker,
+    timestamp: i64,
+) -> Result<MatchOutcome> {
+    let mut outcome = MatchOutcome::default();
+
+    while outcome.taker_remaining(taker) > 0 {
+        let Some(maker) = makers.best().copied() else {
+            break;
+        };
+        if !taker.crosses(maker.price) {
+            break;
+        }
+
+        if maker.is_expired(timestamp) {
+            expire_best(makers, order_book, event_queue, timestamp)?;
+            continue;
+        }
+
+        if maker.trader == taker.trader {
+            let cancelled = match taker.self_trade_behavior {
+                SelfTradeBehavior::CancelTaker => {
+                    outcome.taker_cancelled = true;
+                    break;
+                }
+                SelfTradeBehavior::CancelMaker | SelfTradeBehavior::CancelBoth => {
+                    maker.remaining_quantity()
+                }
+                SelfTradeBehavior::DecrementAndCancel => {
+                    let overlap = outcome
+                        .taker_remaining(taker)
+                        .min(maker.remaining_quantity());
+                    outcome.self_trade_decrement = outcome
+                        .self_trade_decrement
+                        .checked_add(overlap)
+                        .ok_or(ExchangeError::Overflow)?;
+                    overlap
+                }
+            };
+
+            cancel_best(makers, order_book, event_queue, cancelled, timestamp)?;
+            let released = order_book.locked_amount(makers.side(), maker.price, cancelled)?;
+            outcome.maker_refund = outcome
+                .maker_refund
+                .checked_add(released)
+                .ok_or(ExchangeError::Overflow)?;
+
+            msg!(
+                "Self-trade prevented: order {} reduced resting order {} by {}",
+                taker.order_id,
+                maker.order_id,
+                cancelled
+            );
+
+            if taker.self_trade_behavior == SelfTradeBehavior::CancelBoth {
+                outcome.taker_cancelled = true;
+                break;
+            }
+            continue;
+        }
+
+        if !order_book.within_band(maker.price) {
+            order_book.halt(timestamp);
+            outcome.halted = true;
+            outcome.taker_cancelled = true;
+            break;
+        }
+
+        let mut quantity = outcome
+            .taker_remaining(taker)
+            .min(maker.remaining_quantity());
+        if let Some(budget) = taker.quote_budget {
+            let affordable =
+                order_book.affordable_quantity(maker.price, budget - outcome.quote_amount)?;
+            quantity = quantity.min(affordable);
+        }
+        if quantity == 0 {
+            break;
+        }
+
+        record_fill(
+            order_book,
+            event_queue,
+            exchange,
+            &maker,
+            taker,
+            quantity,
+            0,
+            timestamp,
+        )?;
+        fill_best(makers, order_book, quantity)?;
+
+        outcome.base_filled = outcome
+            .base_filled
+            .checked_add(quantity)
+            .ok_or(ExchangeError::Overflow)?;
+        outcome.quote_amount = outcome
+            .quote_amount
+            .checked_add(order_book.quote_amount(maker.price, quantity)?)
+            .ok_or(ExchangeError::Overflow)?;
+    }
+
+    Ok(outcome)
+}
+
+/// Executable volume, imbalance and distance from the reference, best first
+type AuctionRank = (u128, Reverse<u128>, Reverse<u64>);
+
+/// Uncrossing price of a call auction: the in-band price executing the most
+/// volume, then leaving the smallest imbalance, then closest to the reference
+/// price (or the last price when there is no band). `None` if nothing crosses.
+pub fn auction_price(
+    order_book: &OrderBook,
+    bids: &OrderSlab,
+    asks: &OrderSlab,
+    timestamp: i64,
+) -> Option<u64> {
+    let live = |node: &&OrderNode| !node.is_expired(timestamp);
+    let reference = if order_book.reference_price > 0 {
+        order_book.reference_price
+    } else {
+        order_book.last_price
+    };
+
+    let mut best: Option<(AuctionRank, u64)> = None;
+    for price in bids
+        .orders()
+        .iter()
+        .chain(asks.orders())
+        .filter(live)
+        .map(|node| node.price)
+    {
+        if !order_book.within_band(price) {
+            continue;
+        }
+
+        let demand: u128 = bids
+            .orders()
+            .iter()
+            .filter(live)
+            .filter(|bid| bid.price >= price)
+            .map(|bid| bid.remaining_quantity() as u128)
+            .sum();
+        let supply: u128 = asks
+            .orders()
+            .iter()
+            .filter(live)
+            .filter(|ask| ask.price <= price)
+            .map(|ask| ask.remaining_quantity() as u128)
+            .sum();
+        let volume = demand.min(supply);
+        if volume == 0 {
+            continue;
+        }
+
+        let rank = (
+            volume,
+            Reverse(demand.abs_diff(supply)),
+            Reverse(price.abs_diff(reference)),
+        );
+        if best.is_some_and(|(best_rank, _)| rank <= best_rank) {
+            continue;
+        }
+        best = Some((rank, price));
+    }
+
+    best.map(|(_, price)| price)
+}
+
+/// Push the fill event for `quantity` of `maker` and update market statistics.
+/// The maker always sets the price. Each side pays its fee in the asset it
+/// receives: base tokens for the buyer, quote tokens for the seller.
+#[allow(clippy::too_many_arguments)]
+pub fn record_fill(
+    order_book: &mut OrderBook,
+    event_queue: &mut EventQueue,
+    exchange: &Exchange,
+    maker: &OrderNode,
+    taker: &Taker,
+    quantity: u64,
+    price_improvement: u64,
+    timestamp: i64,
+) -> Result<()> {
+    let base_amount = order_book.base_amount(quantity)?;
+    let notional = order_book.quote_amount(maker.price, quantity)?;
+    let (maker_fee, taker_fee) = match taker.side {
+        OrderSide::Bid => (
+            Exchange::fee_for(notional, exchange.maker_fee_bps)?,
+            Exchange::fee_for(base_amount, exchange.taker_fee_bps)?,
+        ),
+        OrderSide::Ask => (
+            Exchange::fee_for(base_amount, exchange.maker_fee_bps)?,
+            Exchange::fee_for(notional, exchange.taker_fee_bps)?,
+        ),
+    };
+    let event_type = if maker.remaining_quantity() == quantity {
+        EventType::Fill
+    } else {
+        EventType::PartialFill
+    };
+
+    event_queue.push(Event {
+        event_type: event_type.to_u8(),
+        taker_side: taker.side.to_u8(),
+        maker: maker.trader,
+        taker: taker.trader,
+        maker_order_id: maker.order_id,
+        taker_order_id: taker.order_id,
+        maker_client_order_id: maker.client_order_id,
+        taker_client_order_id: taker.client_order_id,
+        price: maker.price,
+        quantity,
+        maker_fee,
+        taker_fee,
+        price_improvement,
+        timestamp,
+        ..bytemuck::Zeroable::zeroed()
+    })?;
+
+    order_book.total_volume = order_book
+        .total_volume
+        .checked_add(quantity)
+        .ok_or(ExchangeError::Overflow)?;
+    order_book.last_price = maker.price;
+
+    msg!(
+        "Maker order {} filled by order {}: Price {}, Quantity {}",
+        maker.order_id,
+        taker.order_id,
+        maker.price,
+        quantity
+    );
+
+    Ok(())
+}
+
+/// Apply a fill to the best resting order, removing it once exhausted
+pub fn fill_best(slab: &mut OrderSlab, order_book: &mut OrderBook, quantity: u64) -> Result<()> {
+    let best = &mut slab.nodes[0];
+    best.filled_quantity = best
+        .filled_quantity
+        .checked_add(quantity)
+        .ok_or(ExchangeError::Overflow)?;
+
+    if best.remaining_quantity() == 0 {
+        slab.remove(0);
+        order_book.total_orders = order_book
+            .total_orders
+            .checked_sub(1)
+            .ok_or(ExchangeError::Overflow)?;
+    }
+
+    Ok(())
+}
+
+/// Cancel `quantity` of the best resting order without trading, removing it
+/// once nothing is left, and push the matching out event.
+pub fn cancel_best(
+    slab: &mut OrderSlab,
+    order_book: &mut OrderBook,
+    event_queue: &mut EventQueue,
+    quantity: u64,
+    timestamp: i64,
+) -> Result<()> {
+    let side = slab.side();
+    let best = &mut slab.nodes[0];
+    best.quantity = best
+        .quantity
+        .checked_sub(quantity)
+        .ok_or(ExchangeError::Overflow)?;
+
+    event_queue.push(Event {
+        event_type: EventType::Out.to_u8(),
+        taker_side: side.to_u8(),
+        maker: best.trader,
+        maker_order_id: best.order_id,
+        maker_client_order_id: best.client_order_id,
+        price: best.price,
+        quantity,
+        timestamp,
+        ..bytemuck::Zeroable::zeroed()
+    })?;
+
+    if best.remaining_quantity() == 0 {
+        slab.remove(0);
+        order_book.total_orders = order_book
+            .total_orders
+            .checked_sub(1)
+            .ok_or(ExchangeError::Overflow)?;
+    }
+
+    Ok(())
+}
+
+/// Push the event for a resting order the exchange removed on its owner's
+/// behalf. Its owner is refunded when the event is consumed.
+pub fn evict_order(
+    order_book: &mut OrderBook,
+    event_queue: &mut EventQueue,
+    side: OrderSide,
+    evicted: &OrderNode,
+    timestamp: i64,
+) -> Result<()> {
+    event_queue.push(Event {
+        event_type: EventType::Evict.to_u8(),
+        taker_side: side.to_u8(),
+        maker: evicted.trader,
+        maker_order_id: evicted.order_id,
+        maker_client_order_id: evicted.client_order_id,
+        price: evicted.price,
+        quantity: evicted.remaining_quantity(),
+        timestamp,
+        ..bytemuck::Zeroable::zeroed()
+    })?;
+
+    order_book.total_orders = order_book
+        .total_orders
+        .checked_sub(1)
+        .ok_or(ExchangeError::Overflow)?;
+
+    msg!("Order {} evicted from the book", evicted.order_id);
+
+    Ok(())
+}
+
+/// Take every resting order of `trader` off `slab`, unlocking its collateral in
+/// `open_orders`. The out events are only informational, as in `cancel_order`,
+/// so a full queue does not stop the cancels. Returns how many were cancelled.
+pub fn cancel_trader_orders(
+    slab: &mut OrderSlab,
+    order_book: &mut OrderBook,
+    open_orders: &mut OpenOrders,
+    event_queue: &mut EventQueue,
+    trader: &Pubkey,
+    timestamp: i64,
+) -> Result<u32> {
+    let side = slab.side();
+    let mut cancelled = 0;
+    let mut index = 0;
+
+    while index < slab.count as usize {
+        if slab.nodes[index].trader != *trader {
+            index += 1;
+            continue;
+        }
+        let order = slab.remove(index);
+        let unfilled_quantity = order.remaining_quantity();
+
+        if !event_queue.is_full() {
+            event_queue.push(Event {
+                event_type: EventType::Out.to_u8(),
+                taker_side: side.to_u8(),
+                maker: order.trader,
+                maker_order_id: order.order_id,
+                maker_client_order_id: order.client_order_id,
+                price: order.price,
+                quantity: unfilled_quantity,
+                timestamp,
+                ..bytemuck::Zeroable::zeroed()
+            })?;
+        }
+
+        let released = order_book.locked_amount(side, order.price, unfilled_quantity)?;
+        open_orders.unlock(side, released)?;
+        order_book.total_orders = order_book
+            .total_orders
+            .checked_sub(1)
+            .ok_or(ExchangeError::Overflow)?;
+        cancelled += 1;
+    }
+
+    Ok(cancelled)
+}
+
+/// Take the best resting order off the book on its owner's behalf, refunding
+/// it when the event is consumed
+pub fn evict_best(
+    slab: &mut OrderSlab,
+    order_book: &mut OrderBook,
+    event_queue: &mut EventQueue,
+    timestamp: i64,
+) -> Result<()> {
+    let side = slab.side();
+    let evicted = slab.remove(0);
+    evict_order(order_book, event_queue, side, &evicted, timestamp)
+}
+
+/// Take the best resting order off the book after its expiry
+pub fn expire_best(
+    slab: &mut OrderSlab,
+    order_book: &mut OrderBook,
+    event_queue: &mut EventQueue,
+    timestamp: i64,
+) -> Result<()> {
+    let side = slab.side();
+    let expired = slab.remove(0);
+    expire_order(order_book, event_queue, side, &expired, timestamp)
+}
+
+/// Push the event for a resting order removed after its expiry. Its owner is
+/// refunded when the event is consumed.
+pub fn expire_order(
+    order_book: &mut OrderBook,
+    event_queue: &mut EventQueue,
+    side: OrderSide,
+    expired: &OrderNode,
+    timestamp: i64,
+) -> Result<()> {
+    event_queue.push(Event {
+        event_type: EventType::Expire.to_u8(),
+        taker_side: side.to_u8(),
+        maker: expired.trader,
+        maker_order_id: expired.order_id,
+        maker_client_order_id: expired.client_order_id,
+        price: expired.price,
+        quantity: expired.remaining_quantity(),
+        timestamp,
+        ..bytemuck::Zeroable::zeroed()
+    })?;
+
+    order_book.total_orders = order_book
+        .total_orders
+        .checked_sub(1)
+        .ok_or(ExchangeError::Overflow)?;
+
+    msg!(
+        "Order {} expired and removed from the book",
+        expired.order_id
+    );
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::fixtures::{self, blank};
+
+    const PRICE: u64 = 1_000_000; // one quote lot per base lot
+
+    fn market() -> OrderBook {
+        OrderBook {
+            min_order_size: 1,
+            total_orders: 3,
+            share_multiplier: 1,
+            is_active: true,
+            ..fixtures::market()
+        }
+    }
+
+    fn exchange() -> Exchange {
+        Exchange {
+            authority: Pubkey::new_unique(),
+            fee_collector: Pubkey::new_unique(),
+            maker_fee_bps: 10,
+            taker_fee_bps: 20,
+            total_markets: 1,
+            ..blank(Exchange::LEN)
+        }
+    }
+
+    fn slab(side: OrderSide, orders: &[(u64, Pubkey, u64, u64)]) -> Box<OrderSlab> {
+        let mut slab: Box<OrderSlab> = Box::new(bytemuck::Zeroable::zeroed());
+        slab.side = side.to_u8();
+        for &(order_id, trader, price, quantity) in orders {
+            slab.insert(OrderNode {
+                order_id,
+                client_order_id: 0,
+                trader,
+                price,
+                quantity,
+                filled_quantity: 0,
+                timestamp: 0,
+                expires_at: 0,
+            })
+            .unwrap();
+        }
+        slab
+    }
+
+    fn asks(orders: &[(u64, Pubkey, u64, u64)]) -> Box<OrderSlab> {
+        slab(OrderSide::Ask, orders)
+    }
+
+    fn bids(orders: &[(u64, Pubkey, u64, u64)]) -> Box<OrderSlab> {
+        slab(OrderSide::Bid, orders)
+    }
+
+    fn queue() -> Box<EventQueue> {
+        Box::new(bytemuck::Zeroable::zeroed())
+    }
+
+    fn buy(trader: Pubkey, limit_price: u64, quantity: u64) -> Taker {
+        Taker {
+            order_id: 10,
+            client_order_id: 0,
+            trader,
+            side: OrderSide::Bid,
+            limit_price: Some(limit_price),
+            quantity,
+            quote_budget: None,
+            self_trade_behavior: SelfTradeBehavior::CancelTaker,
+        }
+    }
+
+    #[test]
+    fn fills_the_best_price_first_then_in_time_order() {
+        let (alice, bob) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let mut order_book = market();
+        let mut makers = asks(&[
+            (1, alice, 2 * PRICE, 5),
+            (2, bob, PRICE, 5),
+            (3, alice, PRICE, 5),
+        ]);
+        let mut event_queue = queue();
+
+        let outcome = match_taker(
+            &mut order_book,
+            &mut makers,
+            &mut event_queue,
+            &exchange(),
+            &buy(Pubkey::new_unique(), PRICE, 8),
+            0,
+        )
+        .unwrap();
+
+        assert_eq!(outcome.base_filled, 8);
+        assert_eq!(outcome.quote_amount, 80);
+        assert!(!outcome.taker_cancelled);
+        assert_eq!(event_queue.count, 2);
+        let fill = event_queue.pop_front().unwrap();
+        assert_eq!(fill.event_type(), EventType::Fill);
+        assert_eq!((fill.maker_order_id, fill.quantity), (2, 5));
+        // The buyer pays its fee in base tokens, the seller in quote tokens
+        assert_eq!((fill.taker_fee, fill.maker_fee), (1, 0));
+        let partial = event_queue.pop_front().unwrap();
+        assert_eq!(partial.event_type(), EventType::PartialFill);
+        assert_eq!((partial.maker_order_id, partial.quantity), (3, 3));
+        assert_eq!(makers.best().unwrap().remaining_quantity(), 2);
+        assert_eq!(order_book.total_orders, 2);
+        assert_eq!(order_book.last_price, PRICE);
+    }
+
+    #[test]
+    fn quote_budget_caps_a_market_buy() {
+        let mut order_book = market();
+        let mut makers = asks(&[(1, Pubkey::new_unique(), PRICE, 10)]);
+        let taker = Taker {
+            limit_price: None,
+            quantity: u64::MAX,
+            quote_budget: Some(35),
+            ..buy(Pubkey::new_unique(), 0, 0)
+        };
+
+        let outcome = match_taker(
+            &mut order_book,
+            &mut makers,
+            &mut queue(),
+            &exchange(),
+            &taker,
+            0,
+        )
+        .unwrap();
+
+        assert_eq!(outcome.base_filled, 3);
+        assert_eq!(outcome.quote_amount, 30);
+    }
+
+    #[test]
+    fn expired_makers_are_removed_instead_of_filled() {
+        let mut order_book = market();
+        let mut makers = asks(&[(1, Pubkey::new_unique(), PRICE, 5)]);
+        makers.nodes[0].expires_at = 50;
+        let mut event_queue = queue();
+
+        let outcome = match_taker(
+            &mut order_book,
+            &mut makers,
+            &mut event_queue,
+            &exchange(),
+            &buy(Pubkey::new_unique(), PRICE, 5),
+            50,
+        )
+        .unwrap();
+
+        assert_eq!(outcome.base_filled, 0);
+        assert_eq!(makers.count, 0);
+        assert_eq!(event_queue.front().unwrap().event_type(), EventType::Expire);
+    }
+
+    #[test]
+    fn a_fill_outside_the_band_halts_the_market() {
+        let mut order_book = OrderBook {
+            reference_price: PRICE,
+            price_band_bps: 500,
+            ..market()
+        };
+        let mut makers = asks(&[(1, Pubkey::new_unique(), 2 * PRICE, 5)]);
+
+        let outcome = match_taker(
+            &mut order_book,
+            &mut makers,
+            &mut queue(),
+            &exchange(),
+            &buy(Pubkey::new_unique(), 2 * PRICE, 5),
+            100,
+        )
+        .unwrap();
+
+        assert!(outcome.halted && outcome.taker_cancelled);
+        assert_eq!(outcome.base_filled, 0);
+        assert!(order_book.is_halted(100));
+    }
+
+    #[test]
+    fn cancel_taker_leaves_the_own_maker_resting() {
+        let trader = Pubkey::new_unique();
+        let mut order_book = market();
+        let mut makers = asks(&[(1, trader, PRICE, 5)]);
+
+        let outcome = match_taker(
+            &mut order_book,
+            &mut makers,
+            &mut queue(),
+            &exchange(),
+            &buy(trader, PRICE, 5),
+            0,
+        )
+        .unwrap();
+
+        assert!(outcome.taker_cancelled);
+        assert_eq!(outcome.base_filled, 0);
+        assert_eq!(makers.best().unwrap().remaining_quantity(), 5);
+    }
+
+    #[test]
+    fn cancel_maker_refunds_the_own_order_and_keeps_matching() {
+        let trader = Pubkey::new_unique();
+        let mut order_book = market();
+        let mut makers = asks(&[(1, trader, PRICE, 5), (2, Pubkey::new_unique(), PRICE, 5)]);
+        let taker = Taker {
+            self_trade_behavior: SelfTradeBehavior::CancelMaker,
+            ..buy(trader, PRICE, 5)
+        };
+
+        let outcome = match_taker(
+            &mut order_book,
+            &mut makers,
+            &mut queue(),
+            &exchange(),
+            &taker,
+            0,
+        )
+        .unwrap();
+
+        // The cancelled ask had locked 5 lots of 100 base tokens
+        assert_eq!(outcome.maker_refund, 500);
+        assert_eq!(outcome.base_filled, 5);
+        assert!(!outcome.taker_cancelled);
+        assert_eq!(makers.count, 0);
+    }
+
+    #[test]
+    fn decrement_and_cancel_shrinks_both_orders_by_the_overlap() {
+        let trader = Pubkey::new_unique();
+        let mut order_book = market();
+        let mut makers = asks(&[(1, trader, PRICE, 3)]);
+        let taker = Taker {
+            self_trade_behavior: SelfTradeBehavior::DecrementAndCancel,
+            ..buy(trader, PRICE, 5)
+        };
+
+        let outcome = match_taker(
+            &mut order_book,
+            &mut makers,
+            &mut queue(),
+            &exchange(),
+            &taker,
+            0,
+        )
+        .unwrap();
+
+        assert_eq!(outcome.self_trade_decrement, 3);
+        assert_eq!(outcome.taker_remaining(&taker), 2);
+        assert_eq!(outcome.base_filled, 0);
+        assert_eq!(makers.count, 0);
+    }
+
+    #[test]
+    fn auction_uncrosses_at_the_price_executing_the_most_volume() {
+        let trader = Pubkey::new_unique();
+        let bids = bids(&[(1, trader, 103_000, 10), (2, trader, 101_000, 10)]);
+        let asks = asks(&[(3, trader, 100_000, 5), (4, trader, 102_000, 15)]);
+
+        // 102 matches 10 lots, more than 15 demanded at 101 against 5 offered
+        assert_eq!(auction_price(&market(), &bids, &asks, 0), Some(102_000));
+    }
+
+    #[test]
+    fn auction_volume_ties_go_to_the_smaller_imbalance() {
+        let trader = Pubkey::new_unique();
+        let bids = bids(&[(1, trader, 102_000, 10)]);
+        let asks = asks(&[(2, trader, 100_000, 10), (3, trader, 102_000, 5)]);
+        let order_book = OrderBook {
+            last_price: 102_000,
+            ..market()
+        };
+
+        // Both prices execute 10 lots, but 102 would leave 5 offered unfilled
+        assert_eq!(auction_price(&order_book, &bids, &asks, 0), Some(100_000));
+    }
+
+    #[test]
+    fn auction_then_picks_the_price_closest_to_the_last_trade() {
+        let trader = Pubkey::new_unique();
+        let bids = bids(&[(1, trader, 102_000, 10)]);
+        let asks = asks(&[(2, trader, 100_000, 10)]);
+
+        for (last_price, expected) in [(101_500, 102_000), (100_500, 100_000)] {
+            let order_book = OrderBook {
+                last_price,
+                ..market()
+            };
+            assert_eq!(auction_price(&order_book, &bids, &asks, 0), Some(expected));
+        }
+    }
+
+    #[test]
+    fn auction_ignores_expired_and_out_of_band_orders() {
+        let trader = Pubkey::new_unique();
+        let mut bids = bids(&[(1, trader, 100_000, 10)]);
+        let asks = asks(&[(2, trader, 100_000, 10)]);
+        assert_eq!(auction_price(&market(), &bids, &asks, 0), Some(100_000));
+
+        bids.nodes[0].expires_at = 10;
+        assert_eq!(auction_price(&market(), &bids, &asks, 10), None);
+
+        let order_book = OrderBook {
+            reference_price: 200_000,
+            price_band_bps: 1_000,
+            ..market()
+        };
+        assert_eq!(auction_price(&order_book, &bids, &asks, 0), None);
+    }
+
+    #[test]
+    fn a_blocked_traders_orders_are_cancelled_and_unlocked() {
+        let (blocked, other) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let mut order_book = market();
+        let mut bids = bids(&[(1, other, PRICE, 4), (2, blocked, PRICE, 5)]);
+        let mut asks = asks(&[(3, blocked, 2 * PRICE, 2)]);
+        let mut event_queue = queue();
+        let mut open_orders = OpenOrders {
+            owner: blocked,
+            quote_locked: 50,
+            base_locked: 200,
+            ..blank(OpenOrders::LEN)
+        };
+
+        for slab in [&mut bids, &mut asks] {
+            cancel_trader_orders(
+                slab,
+                &mut order_book,
+                &mut open_orders,
+                &mut event_queue,
+                &blocked,
+                0,
+            )
+            .unwrap();
+        }
+
+        assert_eq!(
+            bids.orders().iter().map(|o| o.order_id).collect::<Vec<_>>(),
+            vec![1]
+        );
+        assert_eq!(asks.count, 0);
+        assert_eq!(order_book.total_orders, 1);
+        assert_eq!((open_orders.quote_free, open_orders.quote_locked), (50, 0));
+        assert_eq!((open_orders.base_free, open_orders.base_locked), (200, 0));
+        assert_eq!(event_queue.count, 2);
+        assert_eq!(event_queue.front().unwrap().event_type(), EventType::Out);
+    }
+}
@@ -1,9 +1,13 @@
+use crate::constants::*;
+use crate::error::ExchangeError;
 use anchor_lang::prelude::*;
 
 /// Exchange configuration and authority
 #[account]
 pub struct Exchange {
     pub authority: Pubkey,
+    pub pending_authority: Pubkey, // proposed successor, default if none
+    pub listing_program: Pubkey, // program whose listing authorities list offerings, default if none
     pub fee_collector: Pubkey,
     pub maker_fee_bps: u16, // basis points (1 bps = 0.01%)
     pub taker_fee_bps: u16,
@@ -15,29 +19,167 @@ pub struct Exchange {
 impl Exchange {
     pub const LEN: usize = 8 + // discriminator
         32 + // authority
+        32 + // pending_authority
+        32 + // listing_program
         32 + // fee_collector
         2 + // maker_fee_bps
         2 + // taker_fee_bps
         8 + // total_markets
         1 + // paused
         1; // bump
+
+    /// Fee charged on `notional` at the given rate, rounded down
+    pub fn fee_for(notional: u64, fee_bps: u16) -> Result<u64> {
+        let fee = (notional as u128)
+            .checked_mul(fee_bps as u128)
+            .ok_or(ExchangeError::Overflow)?
+            / BPS_DENOMINATOR as u128;
+        Ok(u64::try_from(fee).map_err(|_| ExchangeError::Overflow)?)
+    }
+
+    /// Whether `signer` may create an order book for `base_mint`: the authority,
+    /// or the listing program's authority for that mint, which only the offering
+    /// of `base_mint` can sign for
+    pub fn may_list(&self, signer: &Pubkey, base_mint: &Pubkey) -> bool {
+        *signer == self.authority
+            || (self.listing_program != Pubkey::default()
+                && *signer == Self::listing_authority(&self.listing_program, base_mint))
+    }
+
+    /// PDA of `listing_program` that lists markets for `base_mint`
+    pub fn listing_authority(listing_program: &Pubkey, base_mint: &Pubkey) -> Pubkey {
+        Pubkey::find_program_address(
+            &[LISTING_AUTHORITY_SEED, base_mint.as_ref()],
+            listing_program,
+        )
+        .0
+    }
 }
 
-/// Order book for a trading pair (base token vs SOL)
+/// Lot, tick and band settings an order book is created with
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct MarketParams {
+    pub base_lot_size: u64,
+    pub quote_lot_size: u64,
+    pub tick_size: u64,
+    pub min_order_size: u64, // in base lots
+    pub price_band_bps: u16, // applied once a reference price is set
+}
+
+impl MarketParams {
+    pub const LEN: usize = 8 + 8 + 8 + 8 + 2;
+
+    /// Whether `initialize_order_book` accepts these settings. Every
+    /// tick-aligned price must convert to whole quote tokens per lot.
+    pub fn is_valid(&self) -> bool {
+        self.tick_size >= MIN_TICK_SIZE
+            && self.base_lot_size > 0
+            && self.quote_lot_size > 0
+            && (self.tick_size as u128 * self.quote_lot_size as u128)
+                .is_multiple_of(PRICE_SCALE as u128)
+            && self.min_order_size >= MIN_ORDER_SIZE
+            && self.price_band_bps as u64 <= BPS_DENOMINATOR
+    }
+}
+
+/// Daily session times, in seconds after local midnight
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct SessionSchedule {
+    pub utc_offset: i32,     // seconds added to UTC to get exchange local time
+    pub trading_days: u8,    // bit 0 = Monday ... bit 6 = Sunday
+    pub pre_open_start: u32, // orders accepted, no matching
+    pub continuous_start: u32,
+    pub closing_start: u32, // modify and cancel only
+    pub closed_start: u32,  // cancel only until the next pre-open
+}
+
+impl SessionSchedule {
+    pub const LEN: usize = 4 + 1 + 4 * 4;
+
+    pub fn is_valid(&self) -> bool {
+        self.utc_offset.abs() <= MAX_UTC_OFFSET
+            && self.trading_days & 0x80 == 0
+            && self.pre_open_start <= self.continuous_start
+            && self.continuous_start <= self.closing_start
+            && self.closing_start <= self.closed_start
+            && (self.closed_start as i64) <= SECONDS_PER_DAY
+    }
+}
+
+/// Exchange-wide market hours and holiday list
+#[account]
+pub struct TradingCalendar {
+    pub exchange: Pubkey,
+    pub schedule: SessionSchedule,
+    pub holidays: Vec<i64>, // local days since the Unix epoch with no trading
+    pub bump: u8,
+}
+
+impl TradingCalendar {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // exchange
+        SessionSchedule::LEN + // schedule
+        4 + 8 * MAX_HOLIDAYS + // holidays
+        1; // bump
+
+    /// Session phase at unix time `now`, in exchange local time
+    pub fn phase(&self, now: i64) -> SessionPhase {
+        let schedule = &self.schedule;
+        let local = now.saturating_add(schedule.utc_offset as i64);
+        let day = local.div_euclid(SECONDS_PER_DAY);
+        let seconds = local.rem_euclid(SECONDS_PER_DAY);
+
+        // 1970-01-01 was a Thursday
+        let weekday = (day + 3).rem_euclid(7);
+        if schedule.trading_days & (1 << weekday) == 0 || self.holidays.contains(&day) {
+            return SessionPhase::Closed;
+        }
+
+        if seconds < schedule.pre_open_start as i64 {
+            SessionPhase::Closed
+        } else if seconds < schedule.continuous_start as i64 {
+            SessionPhase::PreOpen
+        } else if seconds < schedule.closing_start as i64 {
+            SessionPhase::Continuous
+        } else if seconds < schedule.closed_start as i64 {
+            SessionPhase::Closing
+        } else {
+            SessionPhase::Closed
+        }
+    }
+}
+
+/// Order book for a trading pair (base token vs quote token)
 #[account]
 pub struct OrderBook {
     pub exchange: Pubkey,
     pub base_mint: Pubkey,
     pub base_vault: Pubkey,
-    pub sol_vault: Pubkey, // Vault holding SOL (quote currency)
+    pub quote_mint: Pubkey, // wrapped SOL for SOL-quoted markets
+    pub quote_vault: Pubkey,
+    pub bids: Pubkey, // slab of resting buy orders
+    pub asks: Pubkey, // slab of resting sell orders
+    pub event_queue: Pubkey,
+    pub base_lot_size: u64, // base tokens per lot; order quantities are in lots
+    pub quote_lot_size: u64, // quote tokens per lot
     pub tick_size: u64,
     pub min_order_size: u64,
-    pub bids_head: Option<u64>, // linked list head for buy orders
-    pub asks_head: Option<u64>, // linked list head for sell orders
     pub next_order_id: u64,
+    pub next_trade_id: u64,
     pub total_orders: u64,
     pub total_volume: u64,
     pub last_price: u64,
+    pub reference_price: u64, // centre of the price band, 0 for no band
+    pub price_band_bps: u16,
+    pub halted_until: i64,  // circuit breaker cooldown end, 0 if never tripped
+    pub in_auction: bool,   // orders accumulate without matching until uncrossed
+    pub auction_price: u64, // uncrossing price while fills are in progress, 0 otherwise
+    pub open_orders_count: u32,
+    pub trigger_order_count: u32, // pending trigger orders
+    pub unsettled_trades: u32,
+    pub share_epoch: u32, // corporate actions applied to the base token so far
+    pub pending_rescales: u32, // accounts still to rescale; the market is halted until 0
+    pub share_multiplier: u64, // new shares per old share in the current corporate action
     pub is_active: bool,
     pub bump: u8,
 }
@@ -47,50 +189,330 @@ impl OrderBook {
         32 + // exchange
         32 + // base_mint
         32 + // base_vault
-        32 + // sol_vault
+        32 + // quote_mint
+        32 + // quote_vault
+        32 + // bids
+        32 + // asks
+        32 + // event_queue
+        8 + // base_lot_size
+        8 + // quote_lot_size
         8 + // tick_size
         8 + // min_order_size
-        1 + 8 + // bids_head (Option<u64>)
-        1 + 8 + // asks_head (Option<u64>)
         8 + // next_order_id
+        8 + // next_trade_id
         8 + // total_orders
         8 + // total_volume
         8 + // last_price
+        8 + // reference_price
+        2 + // price_band_bps
+        8 + // halted_until
+        1 + // in_auction
+        8 + // auction_price
+        4 + // open_orders_count
+        4 + // trigger_order_count
+        4 + // unsettled_trades
+        4 + // share_epoch
+        4 + // pending_rescales
+        8 + // share_multiplier
         1 + // is_active
         1; // bump
+
+    /// Base tokens in `quantity` lots
+    pub fn base_amount(&self, quantity: u64) -> Result<u64> {
+        Ok(quantity
+            .checked_mul(self.base_lot_size)
+            .ok_or(ExchangeError::Overflow)?)
+    }
+
+    /// Quote tokens for `quantity` lots at `price`, which counts quote lots per
+    /// base lot with `PRICE_DECIMALS` decimals. Exact for any tick-aligned price.
+    pub fn quote_amount(&self, price: u64, quantity: u64) -> Result<u64> {
+        let amount = (price as u128)
+            .checked_mul(quantity as u128)
+            .and_then(|lots| lots.checked_mul(self.quote_lot_size as u128))
+            .ok_or(ExchangeError::Overflow)?
+            / PRICE_SCALE as u128;
+        Ok(u64::try_from(amount).map_err(|_| ExchangeError::Overflow)?)
+    }
+
+    /// Most lots that `budget` quote tokens buy at `price`
+    pub fn affordable_quantity(&self, price: u64, budget: u64) -> Result<u64> {
+        let lot_price = self.quote_amount(price, 1)?;
+        require!(lot_price > 0, ExchangeError::InvalidPrice);
+        Ok(budget / lot_price)
+    }
+
+    /// Whether `price` lies within `price_band_bps` of the reference price
+    pub fn within_band(&self, price: u64) -> bool {
+        if self.reference_price == 0 {
+            return true;
+        }
+        let reference = self.reference_price as u128;
+        let width = reference * self.price_band_bps as u128 / BPS_DENOMINATOR as u128;
+        let price = price as u128;
+        price >= reference.saturating_sub(width) && price <= reference + width
+    }
+
+    pub fn is_halted(&self, now: i64) -> bool {
+        now < self.halted_until
+    }
+
+    /// Whether a corporate action is still rescaling this market's accounts
+    pub fn is_restating(&self) -> bool {
+        self.pending_rescales > 0
+    }
+
+    /// Trip the circuit breaker, halting the market for the cooldown period
+    pub fn halt(&mut self, now: i64) {
+        self.halted_until = now.saturating_add(CIRCUIT_BREAKER_COOLDOWN);
+        msg!(
+            "Circuit breaker tripped: market halted until {}",
+            self.halted_until
+        );
+    }
+
+    /// Restate the market for `share_multiplier` new shares per old share and
+    /// start rescaling its accounts. The restated tick must still be a valid
+    /// tick, so every resting price divides exactly.
+    pub fn restate(&mut self, share_multiplier: u64) -> Result<()> {
+        require!(
+            share_multiplier >= 2 && self.tick_size.is_multiple_of(share_multiplier),
+            ExchangeError::InvalidCorporateAction
+        );
+        let tick_size = self.tick_size / share_multiplier;
+        require!(
+            tick_size >= MIN_TICK_SIZE
+                && (tick_size as u128 * self.quote_lot_size as u128)
+                    .is_multiple_of(PRICE_SCALE as u128),
+            ExchangeError::InvalidCorporateAction
+        );
+
+        self.tick_size = tick_size;
+        self.min_order_size = self
+            .min_order_size
+            .checked_mul(share_multiplier)
+            .ok_or(ExchangeError::Overflow)?;
+        self.reference_price /= share_multiplier;
+        self.last_price /= share_multiplier;
+        self.auction_price /= share_multiplier;
+        self.share_epoch = self
+            .share_epoch
+            .checked_add(1)
+            .ok_or(ExchangeError::Overflow)?;
+        self.share_multiplier = share_multiplier;
+        self.pending_rescales = self
+            .open_orders_count
+            .checked_add(self.trigger_order_count)
+            .ok_or(ExchangeError::Overflow)?;
+        Ok(())
+    }
+
+    /// Collateral backing `quantity` lots at `price`: quote for bids, base tokens for asks
+    pub fn locked_amount(&self, side: OrderSide, price: u64, quantity: u64) -> Result<u64> {
+        match side {
+            OrderSide::Bid => self.quote_amount(price, quantity),
+            OrderSide::Ask => self.base_amount(quantity),
+        }
+    }
 }
 
-/// Individual order in the order book
-#[account]
-pub struct Order {
-    pub order_book: Pubkey,
-    pub trader: Pubkey,
+/// Resting order stored inline in an order slab
+#[zero_copy]
+pub struct OrderNode {
     pub order_id: u64,
-    pub side: OrderSide,
-    pub order_type: OrderType,
+    pub client_order_id: u64, // trader-chosen id, 0 if none
+    pub trader: Pubkey,
+    pub price: u64,    // quote lots per base lot, see `OrderBook::quote_amount`
+    pub quantity: u64, // base lots
+    pub filled_quantity: u64,
+    pub timestamp: i64,
+    pub expires_at: i64, // unix timestamp, 0 for good-till-cancelled
+}
+
+impl OrderNode {
+    pub fn remaining_quantity(&self) -> u64 {
+        self.quantity.saturating_sub(self.filled_quantity)
+    }
+
+    pub fn is_expired(&self, now: i64) -> bool {
+        self.expires_at != 0 && now >= self.expires_at
+    }
+
+    /// Restate for a corporate action; prices are tick multiples, so dividing
+    /// them keeps every lock exact
+    pub fn rescale(&mut self, multiplier: u64) -> Result<()> {
+        self.price /= multiplier;
+        self.quantity = self
+            .quantity
+            .checked_mul(multiplier)
+            .ok_or(ExchangeError::Overflow)?;
+        self.filled_quantity = self
+            .filled_quantity
+            .checked_mul(multiplier)
+            .ok_or(ExchangeError::Overflow)?;
+        Ok(())
+    }
+}
+
+/// One side of an order book. Resting orders are kept sorted best price
+/// first and FIFO within a price level, so `nodes[0]` is always next to match.
+#[account(zero_copy)]
+pub struct OrderSlab {
+    pub order_book: Pubkey,
+    pub count: u32,
+    pub side: u8, // 0 = Bid, 1 = Ask
+    pub padding: [u8; 3],
+    pub nodes: [OrderNode; SLAB_CAPACITY],
+}
+
+impl OrderSlab {
+    pub const LEN: usize = 8 + // discriminator
+        std::mem::size_of::<OrderSlab>();
+
+    pub fn side(&self) -> OrderSide {
+        OrderSide::from_u8(self.side)
+    }
+
+    pub fn orders(&self) -> &[OrderNode] {
+        &self.nodes[..self.count as usize]
+    }
+
+    pub fn best(&self) -> Option<&OrderNode> {
+        self.orders().first()
+    }
+
+    pub fn find(&self, order_id: u64) -> Option<usize> {
+        self.orders()
+            .iter()
+            .position(|node| node.order_id == order_id)
+    }
+
+    /// Resting order of `trader` carrying `client_order_id`
+    pub fn find_by_client_id(&self, trader: &Pubkey, client_order_id: u64) -> Option<usize> {
+        self.orders()
+            .iter()
+            .position(|node| node.trader == *trader && node.client_order_id == client_order_id)
+    }
+
+    /// Insert behind every order at the same or a better price. A full slab
+    /// makes room by evicting its worst order, returned for the caller to
+    /// refund; an order that does not outrank the worst one is rejected.
+    pub fn insert(&mut self, node: OrderNode) -> Result<(usize, Option<OrderNode>)> {
+        let side = self.side();
+        let evicted = if self.count as usize == SLAB_CAPACITY {
+            let worst = self.nodes[SLAB_CAPACITY - 1];
+            require!(
+                side.outranks(node.price, worst.price),
+                ExchangeError::OrderBookFull
+            );
+            Some(self.remove(SLAB_CAPACITY - 1))
+        } else {
+            None
+        };
+
+        let len = self.count as usize;
+        let index = self
+            .orders()
+            .iter()
+            .position(|resting| side.outranks(node.price, resting.price))
+            .unwrap_or(len);
+
+        self.nodes.copy_within(index..len, index + 1);
+        self.nodes[index] = node;
+        self.count += 1;
+
+        Ok((index, evicted))
+    }
+
+    pub fn remove(&mut self, index: usize) -> OrderNode {
+        let len = self.count as usize;
+        let node = self.nodes[index];
+
+        self.nodes.copy_within(index + 1..len, index);
+        self.nodes[len - 1] = bytemuck::Zeroable::zeroed();
+        self.count -= 1;
+
+        node
+    }
+}
+
+/// Record of something that happened to an order, in matching order.
+/// For `Out`, `Expire` and `Trigger` events `maker` is the order owner and the
+/// taker fields are unused.
+#[zero_copy]
+pub struct Event {
+    pub event_type: u8, // EventType
+    pub taker_side: u8, // OrderSide
+    pub padding: [u8; 6],
+    pub maker: Pubkey,
+    pub taker: Pubkey,
+    pub maker_order_id: u64,
+    pub taker_order_id: u64,
+    pub maker_client_order_id: u64,
+    pub taker_client_order_id: u64,
     pub price: u64,
     pub quantity: u64,
-    pub filled_quantity: u64,
+    pub maker_fee: u64,
+    pub taker_fee: u64,
+    pub price_improvement: u64,
     pub timestamp: i64,
-    pub next: Option<u64>, // next order in linked list
-    pub prev: Option<u64>, // previous order in linked list
-    pub is_active: bool,
+    pub seq_num: u64,
 }
 
-impl Order {
+impl Event {
+    pub fn event_type(&self) -> EventType {
+        EventType::from_u8(self.event_type)
+    }
+
+    pub fn taker_side(&self) -> OrderSide {
+        OrderSide::from_u8(self.taker_side)
+    }
+}
+
+/// Per-market ring buffer of fill and out events, drained by `consume_events`
+#[account(zero_copy)]
+pub struct EventQueue {
+    pub order_book: Pubkey,
+    pub head: u64,
+    pub count: u64,
+    pub seq_num: u64, // sequence number assigned to the next pushed event
+    pub events: [Event; EVENT_QUEUE_CAPACITY],
+}
+
+impl EventQueue {
     pub const LEN: usize = 8 + // discriminator
-        32 + // order_book
-        32 + // trader
-        8 + // order_id
-        1 + // side
-        1 + // order_type
-        8 + // price
-        8 + // quantity
-        8 + // filled_quantity
-        8 + // timestamp
-        1 + 8 + // next (Option<u64>)
-        1 + 8 + // prev (Option<u64>)
-        1; // is_active
+        std::mem::size_of::<EventQueue>();
+
+    pub fn is_full(&self) -> bool {
+        self.count as usize == EVENT_QUEUE_CAPACITY
+    }
+
+    pub fn front(&self) -> Option<&Event> {
+        if self.count == 0 {
+            None
+        } else {
+            Some(&self.events[self.head as usize])
+        }
+    }
+
+    pub fn push(&mut self, mut event: Event) -> Result<()> {
+        require!(!self.is_full(), ExchangeError::EventQueueFull);
+
+        event.seq_num = self.seq_num;
+        let tail = (self.head + self.count) as usize % EVENT_QUEUE_CAPACITY;
+        self.events[tail] = event;
+        self.count += 1;
+        self.seq_num += 1;
+
+        Ok(())
+    }
+
+    pub fn pop_front(&mut self) -> Option<Event> {
+        let event = *self.front()?;
+        self.head = (self.head + 1) % EVENT_QUEUE_CAPACITY as u64;
+        self.count -= 1;
+        Some(event)
+    }
 }
 
 /// User trading account for tracking positions
@@ -116,7 +538,219 @@ impl TradingAccount {
         1; // bump
 }
 
-/// Trade execution record
+/// Compliance officers allowed to write KYC entries
+#[account]
+pub struct ComplianceRegistry {
+    pub exchange: Pubkey,
+    pub officers: Vec<Pubkey>,
+    pub bump: u8,
+}
+
+impl ComplianceRegistry {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // exchange
+        4 + 32 * MAX_COMPLIANCE_OFFICERS + // officers
+        1; // bump
+
+    pub fn is_officer(&self, key: &Pubkey) -> bool {
+        self.officers.contains(key)
+    }
+}
+
+/// KYC record of a wallet. Opening a trading account and placing orders
+/// require a verified entry that has not expired.
+#[account]
+pub struct KycEntry {
+    pub wallet: Pubkey,
+    pub status: KycStatus,
+    pub category: InvestorCategory,
+    pub jurisdiction: [u8; 2], // ISO 3166-1 alpha-2 country code
+    pub expires_at: i64,
+    pub verified_by: Pubkey, // compliance officer who last wrote the entry
+    pub updated_at: i64,
+    pub bump: u8,
+}
+
+impl KycEntry {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // wallet
+        1 + // status
+        1 + // category
+        2 + // jurisdiction
+        8 + // expires_at
+        32 + // verified_by
+        8 + // updated_at
+        1; // bump
+
+    pub fn is_valid(&self, now: i64) -> bool {
+        self.status == KycStatus::Verified && now < self.expires_at
+    }
+
+    pub fn is_valid_jurisdiction(jurisdiction: &[u8; 2]) -> bool {
+        jurisdiction.iter().all(u8::is_ascii_uppercase)
+    }
+}
+
+/// Conditional order waiting for `OrderBook.last_price` to cross its trigger.
+/// Collateral is locked in the trader's open orders account; this account holds the keeper reward.
+#[account]
+pub struct TriggerOrder {
+    pub order_book: Pubkey,
+    pub trader: Pubkey,
+    pub trigger_id: u64, // chosen by the trader, unique per trader and book
+    pub side: OrderSide,
+    pub order_type: OrderType, // Limit or Market once triggered
+    pub direction: TriggerDirection,
+    pub trigger_price: u64,
+    pub limit_price: u64, // 0 for market orders
+    pub quantity: u64,
+    pub locked_amount: u64, // quote for bids, base tokens for asks
+    pub self_trade_behavior: SelfTradeBehavior,
+    pub client_order_id: u64,
+    pub keeper_reward: u64, // lamports paid to whoever activates the order
+    pub created_at: i64,
+    pub share_epoch: u32, // order book share epoch the prices and quantity are counted in
+    pub bump: u8,
+}
+
+impl TriggerOrder {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // order_book
+        32 + // trader
+        8 + // trigger_id
+        1 + // side
+        1 + // order_type
+        1 + // direction
+        8 + // trigger_price
+        8 + // limit_price
+        8 + // quantity
+        8 + // locked_amount
+        1 + // self_trade_behavior
+        8 + // client_order_id
+        8 + // keeper_reward
+        8 + // created_at
+        4 + // share_epoch
+        1; // bump
+
+    pub fn is_triggered(&self, last_price: u64) -> bool {
+        if last_price == 0 {
+            return false;
+        }
+        match self.direction {
+            TriggerDirection::Above => last_price >= self.trigger_price,
+            TriggerDirection::Below => last_price <= self.trigger_price,
+        }
+    }
+
+    /// Restate for a corporate action. Bids lock the same quote either way;
+    /// asks lock base tokens, which the vault now holds `multiplier` times.
+    pub fn rescale(&mut self, multiplier: u64, share_epoch: u32) -> Result<()> {
+        self.trigger_price /= multiplier;
+        self.limit_price /= multiplier;
+        self.quantity = self
+            .quantity
+            .checked_mul(multiplier)
+            .ok_or(ExchangeError::Overflow)?;
+        if self.side == OrderSide::Ask {
+            self.locked_amount = self
+                .locked_amount
+                .checked_mul(multiplier)
+                .ok_or(ExchangeError::Overflow)?;
+        }
+        self.share_epoch = share_epoch;
+        Ok(())
+    }
+}
+
+/// A trader's funds on one market. Free balances back new orders and can be
+/// withdrawn; locked balances back resting orders and unsettled trades.
+/// Bid orders lock quote tokens, ask orders lock base tokens.
+#[account]
+pub struct OpenOrders {
+    pub order_book: Pubkey,
+    pub owner: Pubkey,
+    pub base_free: u64,
+    pub base_locked: u64,
+    pub quote_free: u64,
+    pub quote_locked: u64,
+    pub share_epoch: u32, // order book share epoch the base balances are counted in
+    pub bump: u8,
+}
+
+impl OpenOrders {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // order_book
+        32 + // owner
+        8 + // base_free
+        8 + // base_locked
+        8 + // quote_free
+        8 + // quote_locked
+        4 + // share_epoch
+        1; // bump
+
+    /// Free and locked balances of the asset an order on `side` is backed by
+    fn collateral(&mut self, side: OrderSide) -> (&mut u64, &mut u64) {
+        match side {
+            OrderSide::Bid => (&mut self.quote_free, &mut self.quote_locked),
+            OrderSide::Ask => (&mut self.base_free, &mut self.base_locked),
+        }
+    }
+
+    pub fn lock(&mut self, side: OrderSide, amount: u64) -> Result<()> {
+        let (free, locked) = self.collateral(side);
+        *free = free
+            .checked_sub(amount)
+            .ok_or(ExchangeError::InsufficientFunds)?;
+        *locked = locked.checked_add(amount).ok_or(ExchangeError::Overflow)?;
+        Ok(())
+    }
+
+    pub fn unlock(&mut self, side: OrderSide, amount: u64) -> Result<()> {
+        let (free, locked) = self.collateral(side);
+        *locked = locked.checked_sub(amount).ok_or(ExchangeError::Overflow)?;
+        *free = free.checked_add(amount).ok_or(ExchangeError::Overflow)?;
+        Ok(())
+    }
+
+    /// Locked funds paid away by a settled trade
+    pub fn spend_locked(&mut self, side: OrderSide, amount: u64) -> Result<()> {
+        let (_, locked) = self.collateral(side);
+        *locked = locked.checked_sub(amount).ok_or(ExchangeError::Overflow)?;
+        Ok(())
+    }
+
+    pub fn credit_base(&mut self, amount: u64) -> Result<()> {
+        self.base_free = self
+            .base_free
+            .checked_add(amount)
+            .ok_or(ExchangeError::Overflow)?;
+        Ok(())
+    }
+
+    pub fn credit_quote(&mut self, amount: u64) -> Result<()> {
+        self.quote_free = self
+            .quote_free
+            .checked_add(amount)
+            .ok_or(ExchangeError::Overflow)?;
+        Ok(())
+    }
+
+    /// Restate for a corporate action; the base vault was topped up by the same multiple
+    pub fn rescale(&mut self, multiplier: u64, share_epoch: u32) -> Result<()> {
+        self.base_free = self
+            .base_free
+            .checked_mul(multiplier)
+            .ok_or(ExchangeError::Overflow)?;
+        self.base_locked = self
+            .base_locked
+            .checked_mul(multiplier)
+            .ok_or(ExchangeError::Overflow)?;
+        self.share_epoch = share_epoch;
+        Ok(())
+    }
+}
+
+/// Trade execution record, closed once settled
 #[account]
 pub struct Trade {
     pub order_book: Pubkey,
@@ -125,12 +759,14 @@ pub struct Trade {
     pub taker_order_id: u64,
     pub maker: Pubkey,
     pub taker: Pubkey,
+    pub taker_side: OrderSide,
     pub price: u64,
     pub quantity: u64,
-    pub maker_fee: u64,
-    pub taker_fee: u64,
+    pub maker_fee: u64,         // charged in the asset the maker receives
+    pub taker_fee: u64,         // charged in the asset the taker receives
+    pub price_improvement: u64, // quote locked by the buyer above the trade price
     pub timestamp: i64,
-    pub settled: bool,
+    pub rent_payer: Pubkey, // cranker who created the record, refunded on settlement
 }
 
 impl Trade {
@@ -141,12 +777,36 @@ impl Trade {
         8 + // taker_order_id
         32 + // maker
         32 + // taker
+        1 + // taker_side
         8 + // price
         8 + // quantity
         8 + // maker_fee
         8 + // taker_fee
+        8 + // price_improvement
         8 + // timestamp
-        1; // settled
+        32; // rent_payer
+
+    pub fn buyer(&self) -> Pubkey {
+        match self.taker_side {
+            OrderSide::Bid => self.taker,
+            OrderSide::Ask => self.maker,
+        }
+    }
+
+    pub fn seller(&self) -> Pubkey {
+        match self.taker_side {
+            OrderSide::Bid => self.maker,
+            OrderSide::Ask => self.taker,
+        }
+    }
+
+    /// Fees charged to the buyer (in base tokens) and the seller (in quote tokens)
+    pub fn buyer_and_seller_fees(&self) -> (u64, u64) {
+        match self.taker_side {
+            OrderSide::Bid => (self.taker_fee, self.maker_fee),
+            OrderSide::Ask => (self.maker_fee, self.taker_fee),
+        }
+    }
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
@@ -155,10 +815,624 @@ pub enum OrderSide {
     Ask, // Sell
 }
 
+impl OrderSide {
+    pub fn to_u8(self) -> u8 {
+        match self {
+            OrderSide::Bid => 0,
+            OrderSide::Ask => 1,
+        }
+    }
+
+    pub fn from_u8(value: u8) -> Self {
+        if value == 0 {
+            OrderSide::Bid
+        } else {
+            OrderSide::Ask
+        }
+    }
+
+    pub fn opposite(self) -> Self {
+        match self {
+            OrderSide::Bid => OrderSide::Ask,
+            OrderSide::Ask => OrderSide::Bid,
+        }
+    }
+
+    /// Whether `price` ranks strictly ahead of `other` on this side of the book
+    pub fn outranks(&self, price: u64, other: u64) -> bool {
+        match self {
+            OrderSide::Bid => price > other,
+            OrderSide::Ask => price < other,
+        }
+    }
+}
+
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
 pub enum OrderType {
     Limit,
     Market,
     PostOnly,
     ImmediateOrCancel,
+    FillOrKill,
+}
+
+/// Which way the last traded price has to move to activate a trigger order
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum TriggerDirection {
+    Above, // last price at or above the trigger, e.g. a buy stop
+    Below, // last price at or below the trigger, e.g. a sell stop-loss
+}
+
+/// What to do when an incoming order would match a resting order from the same trader
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum SelfTradeBehavior {
+    CancelTaker,        // stop matching and drop the incoming remainder
+    CancelMaker,        // cancel the resting order and keep matching
+    CancelBoth,         // cancel the resting order and drop the incoming remainder
+    DecrementAndCancel, // reduce both by the overlap without trading
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum EventType {
+    Fill,        // maker order completely filled
+    PartialFill, // maker order still resting after the fill
+    Out,         // order cancelled or reduced without trading
+    Expire,      // expired order evicted by matching, refunded when consumed
+    Trigger,     // trigger order activated into a live order
+    Evict,       // order removed by the exchange, refunded when consumed
+}
+
+impl EventType {
+    pub fn to_u8(self) -> u8 {
+        match self {
+            EventType::Fill => 0,
+            EventType::PartialFill => 1,
+            EventType::Out => 2,
+            EventType::Expire => 3,
+            EventType::Trigger => 4,
+            EventType::Evict => 5,
+        }
+    }
+
+    pub fn from_u8(value: u8) -> Self {
+        match value {
+            0 => EventType::Fill,
+            1 => EventType::PartialFill,
+            2 => EventType::Out,
+            3 => EventType::Expire,
+            4 => EventType::Trigger,
+            _ => EventType::Evict,
+        }
+    }
+
+    pub fn is_fill(self) -> bool {
+        matches!(self, EventType::Fill | EventType::PartialFill)
+    }
+
+    /// The removed order's collateral is still locked until the event is consumed
+    pub fn is_refund(self) -> bool {
+        matches!(self, EventType::Expire | EventType::Evict)
+    }
+}
+
+/// Part of the trading day, deciding which instructions a market accepts.
+/// Cancels are accepted in every phase.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum SessionPhase {
+    PreOpen,    // limit and post-only orders rest without matching
+    Continuous, // normal trading
+    Closing,    // resting orders can be modified but not added
+    Closed,     // cancel only
+}
+
+impl SessionPhase {
+    pub fn allows_order_entry(self) -> bool {
+        matches!(self, SessionPhase::PreOpen | SessionPhase::Continuous)
+    }
+
+    pub fn allows_modification(self) -> bool {
+        !matches!(self, SessionPhase::Closed)
+    }
+
+    pub fn allows_matching(self) -> bool {
+        matches!(self, SessionPhase::Continuous)
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum KycStatus {
+    Verified,  // may trade until the entry expires
+    Suspended, // blocked until reinstated
+    Revoked,   // blocked permanently
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum InvestorCategory {
+    Retail,
+    Professional,
+    Institutional,
+}
+
+/// Account fixtures shared by the unit tests
+#[cfg(test)]
+pub(crate) mod fixtures {
+    use super::*;
+
+    /// An account with every field zeroed, `len` including the discriminator
+    pub fn blank<T: AnchorDeserialize>(len: usize) -> T {
+        T::try_from_slice(&vec![0; len - 8]).unwrap()
+    }
+
+    /// 100 base tokens and 10 quote tokens per lot
+    pub fn market() -> OrderBook {
+        OrderBook {
+            base_lot_size: 100,
+            quote_lot_size: 10,
+            tick_size: 1_000,
+            ..blank(OrderBook::LEN)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::fixtures::*;
+    use super::*;
+
+    fn node(order_id: u64, price: u64) -> OrderNode {
+        OrderNode {
+            order_id,
+            client_order_id: 0,
+            trader: Pubkey::new_unique(),
+            price,
+            quantity: 10,
+            filled_quantity: 0,
+            timestamp: 0,
+            expires_at: 0,
+        }
+    }
+
+    fn slab(side: OrderSide) -> Box<OrderSlab> {
+        let mut slab: Box<OrderSlab> = Box::new(bytemuck::Zeroable::zeroed());
+        slab.side = side.to_u8();
+        slab
+    }
+
+    fn order_ids(slab: &OrderSlab) -> Vec<u64> {
+        slab.orders().iter().map(|node| node.order_id).collect()
+    }
+
+    #[test]
+    fn bids_rest_best_price_first_then_fifo() {
+        let mut bids = slab(OrderSide::Bid);
+        bids.insert(node(1, 100)).unwrap();
+        bids.insert(node(2, 120)).unwrap();
+        bids.insert(node(3, 100)).unwrap();
+        bids.insert(node(4, 110)).unwrap();
+
+        assert_eq!(order_ids(&bids), vec![2, 4, 1, 3]);
+        assert_eq!(bids.best().unwrap().order_id, 2);
+    }
+
+    #[test]
+    fn asks_rest_lowest_price_first() {
+        let mut asks = slab(OrderSide::Ask);
+        asks.insert(node(1, 120)).unwrap();
+        asks.insert(node(2, 100)).unwrap();
+        asks.insert(node(3, 120)).unwrap();
+
+        assert_eq!(order_ids(&asks), vec![2, 1, 3]);
+    }
+
+    #[test]
+    fn remove_keeps_order_and_clears_the_tail() {
+        let mut asks = slab(OrderSide::Ask);
+        for (order_id, price) in [(1, 100), (2, 101), (3, 102)] {
+            asks.insert(node(order_id, price)).unwrap();
+        }
+
+        let removed = asks.remove(1);
+
+        assert_eq!(removed.order_id, 2);
+        assert_eq!(order_ids(&asks), vec![1, 3]);
+        assert_eq!(asks.nodes[2].order_id, 0);
+        assert_eq!(asks.find(3), Some(1));
+        assert_eq!(asks.find(2), None);
+    }
+
+    #[test]
+    fn full_slab_evicts_its_worst_order_for_a_better_one() {
+        let mut bids = slab(OrderSide::Bid);
+        for order_id in 0..SLAB_CAPACITY as u64 {
+            let (_, evicted) = bids.insert(node(order_id, 1_000 - order_id)).unwrap();
+            assert!(evicted.is_none());
+        }
+
+        let (index, evicted) = bids.insert(node(999, 2_000)).unwrap();
+
+        assert_eq!(index, 0);
+        assert_eq!(evicted.unwrap().order_id, SLAB_CAPACITY as u64 - 1);
+        assert_eq!(bids.count as usize, SLAB_CAPACITY);
+    }
+
+    #[test]
+    fn full_slab_rejects_an_order_no_better_than_its_worst() {
+        let mut asks = slab(OrderSide::Ask);
+        for order_id in 0..SLAB_CAPACITY as u64 {
+            asks.insert(node(order_id, 100 + order_id)).unwrap();
+        }
+        let worst = 100 + SLAB_CAPACITY as u64 - 1;
+
+        assert!(asks.insert(node(999, worst)).is_err());
+        assert!(asks.insert(node(999, worst + 1)).is_err());
+        assert_eq!(asks.count as usize, SLAB_CAPACITY);
+    }
+
+    #[test]
+    fn good_till_time_orders_expire_at_their_deadline() {
+        let mut order = node(1, 100);
+        assert!(!order.is_expired(i64::MAX));
+
+        order.expires_at = 1_000;
+        assert!(!order.is_expired(999));
+        assert!(order.is_expired(1_000));
+    }
+
+    #[test]
+    fn trigger_orders_fire_once_the_last_price_crosses() {
+        let stop = TriggerOrder {
+            direction: TriggerDirection::Below,
+            trigger_price: 100,
+            ..blank(TriggerOrder::LEN)
+        };
+        assert!(!stop.is_triggered(101));
+        assert!(stop.is_triggered(100));
+        assert!(stop.is_triggered(99));
+
+        let take_profit = TriggerOrder {
+            direction: TriggerDirection::Above,
+            ..stop
+        };
+        assert!(!take_profit.is_triggered(99));
+        assert!(take_profit.is_triggered(100));
+    }
+
+    #[test]
+    fn trigger_orders_wait_for_a_first_trade() {
+        let stop = TriggerOrder {
+            direction: TriggerDirection::Below,
+            trigger_price: 100,
+            ..blank(TriggerOrder::LEN)
+        };
+        assert!(!stop.is_triggered(0));
+    }
+
+    #[test]
+    fn fees_round_down_in_basis_points() {
+        assert_eq!(Exchange::fee_for(1_000_000, 25).unwrap(), 2_500);
+        assert_eq!(Exchange::fee_for(399, 25).unwrap(), 0);
+        assert_eq!(Exchange::fee_for(u64::MAX, 0).unwrap(), 0);
+    }
+
+    #[test]
+    fn buyers_and_sellers_pay_the_fee_of_their_role() {
+        let trade = Trade {
+            maker: Pubkey::new_unique(),
+            taker: Pubkey::new_unique(),
+            taker_side: OrderSide::Bid,
+            maker_fee: 1,
+            taker_fee: 2,
+            ..blank(Trade::LEN)
+        };
+        assert_eq!(trade.buyer(), trade.taker);
+        assert_eq!(trade.buyer_and_seller_fees(), (2, 1));
+
+        let trade = Trade {
+            taker_side: OrderSide::Ask,
+            ..trade
+        };
+        assert_eq!(trade.buyer(), trade.maker);
+        assert_eq!(trade.seller(), trade.taker);
+        assert_eq!(trade.buyer_and_seller_fees(), (1, 2));
+    }
+
+    #[test]
+    fn locking_moves_collateral_between_free_and_locked() {
+        let mut open_orders = OpenOrders {
+            base_free: 50,
+            quote_free: 1_000,
+            ..blank(OpenOrders::LEN)
+        };
+
+        open_orders.lock(OrderSide::Bid, 400).unwrap();
+        open_orders.lock(OrderSide::Ask, 50).unwrap();
+        assert_eq!(
+            (open_orders.quote_free, open_orders.quote_locked),
+            (600, 400)
+        );
+        assert_eq!((open_orders.base_free, open_orders.base_locked), (0, 50));
+
+        open_orders.unlock(OrderSide::Bid, 100).unwrap();
+        open_orders.spend_locked(OrderSide::Bid, 300).unwrap();
+        assert_eq!((open_orders.quote_free, open_orders.quote_locked), (700, 0));
+    }
+
+    #[test]
+    fn locking_more_than_the_free_balance_fails() {
+        let mut open_orders = OpenOrders {
+            quote_free: 10,
+            ..blank(OpenOrders::LEN)
+        };
+
+        assert!(open_orders.lock(OrderSide::Bid, 11).is_err());
+        assert!(open_orders.unlock(OrderSide::Ask, 1).is_err());
+        assert_eq!(open_orders.quote_free, 10);
+    }
+
+    #[test]
+    fn lot_prices_convert_to_whole_token_amounts() {
+        let order_book = market();
+        // 2.5 quote lots per base lot
+        let price = 2 * PRICE_SCALE + PRICE_SCALE / 2;
+
+        assert_eq!(order_book.base_amount(3).unwrap(), 300);
+        assert_eq!(order_book.quote_amount(price, 4).unwrap(), 100);
+        assert_eq!(
+            order_book.locked_amount(OrderSide::Bid, price, 4).unwrap(),
+            100
+        );
+        assert_eq!(
+            order_book.locked_amount(OrderSide::Ask, price, 4).unwrap(),
+            400
+        );
+    }
+
+    #[test]
+    fn affordable_quantity_rounds_down_to_whole_lots() {
+        let order_book = market();
+
+        assert_eq!(order_book.affordable_quantity(PRICE_SCALE, 99).unwrap(), 9);
+        assert!(order_book.affordable_quantity(0, 99).is_err());
+    }
+
+    #[test]
+    fn quote_amounts_overflowing_u64_fail() {
+        assert!(market().quote_amount(u64::MAX, u64::MAX).is_err());
+    }
+
+    #[test]
+    fn price_band_is_inclusive_around_the_reference() {
+        let mut order_book = market();
+        assert!(order_book.within_band(u64::MAX));
+
+        order_book.reference_price = 1_000_000;
+        order_book.price_band_bps = 1_000;
+        assert!(order_book.within_band(900_000));
+        assert!(order_book.within_band(1_100_000));
+        assert!(!order_book.within_band(899_999));
+        assert!(!order_book.within_band(1_100_001));
+    }
+
+    #[test]
+    fn circuit_breaker_halts_for_the_cooldown() {
+        let mut order_book = market();
+        assert!(!order_book.is_halted(0));
+
+        order_book.halt(1_000);
+        assert!(order_book.is_halted(1_000 + CIRCUIT_BREAKER_COOLDOWN - 1));
+        assert!(!order_book.is_halted(1_000 + CIRCUIT_BREAKER_COOLDOWN));
+    }
+
+    /// Weekdays 09:00 pre-open, 09:15 open, 15:30 closing, 16:00 closed at UTC+5:30
+    fn calendar(holidays: Vec<i64>) -> TradingCalendar {
+        TradingCalendar {
+            exchange: Pubkey::new_unique(),
+            schedule: SessionSchedule {
+                utc_offset: 19_800,
+                trading_days: 0b001_1111,
+                pre_open_start: 32_400,
+                continuous_start: 33_300,
+                closing_start: 55_800,
+                closed_start: 57_600,
+            },
+            holidays,
+            bump: 0,
+        }
+    }
+
+    /// Unix time of `seconds` after local midnight on local day `day`
+    fn local(day: i64, seconds: i64) -> i64 {
+        day * SECONDS_PER_DAY + seconds - 19_800
+    }
+
+    #[test]
+    fn session_phases_follow_local_time() {
+        let calendar = calendar(vec![]);
+        let monday = 4;
+
+        assert_eq!(calendar.phase(local(monday, 32_399)), SessionPhase::Closed);
+        assert_eq!(calendar.phase(local(monday, 32_400)), SessionPhase::PreOpen);
+        assert_eq!(
+            calendar.phase(local(monday, 33_300)),
+            SessionPhase::Continuous
+        );
+        assert_eq!(calendar.phase(local(monday, 55_800)), SessionPhase::Closing);
+        assert_eq!(calendar.phase(local(monday, 57_600)), SessionPhase::Closed);
+    }
+
+    #[test]
+    fn weekends_and_holidays_stay_closed() {
+        let (tuesday, saturday) = (5, 9);
+        let calendar = calendar(vec![tuesday]);
+
+        assert_eq!(
+            calendar.phase(local(saturday, 40_000)),
+            SessionPhase::Closed
+        );
+        assert_eq!(calendar.phase(local(tuesday, 40_000)), SessionPhase::Closed);
+        assert_eq!(
+            calendar.phase(local(tuesday + 1, 40_000)),
+            SessionPhase::Continuous
+        );
+    }
+
+    #[test]
+    fn market_params_need_ticks_worth_whole_quote_tokens() {
+        let params = MarketParams {
+            base_lot_size: 100,
+            quote_lot_size: 10,
+            tick_size: 100_000,
+            min_order_size: 1,
+            price_band_bps: 1_000,
+        };
+        assert!(params.is_valid());
+
+        assert!(!MarketParams {
+            tick_size: 10_000,
+            ..params
+        }
+        .is_valid());
+        assert!(!MarketParams {
+            base_lot_size: 0,
+            ..params
+        }
+        .is_valid());
+        assert!(!MarketParams {
+            price_band_bps: 10_001,
+            ..params
+        }
+        .is_valid());
+    }
+
+    #[test]
+    fn only_the_authority_or_the_mints_listing_authority_may_list() {
+        let mut exchange = Exchange {
+            authority: Pubkey::new_unique(),
+            ..blank(Exchange::LEN)
+        };
+        let (mint, other_mint) = (Pubkey::new_unique(), Pubkey::new_unique());
+        assert!(exchange.may_list(&exchange.authority, &mint));
+        assert!(!exchange.may_list(&Pubkey::default(), &mint));
+
+        let listing_program = Pubkey::new_unique();
+        let listing_authority = Exchange::listing_authority(&listing_program, &mint);
+        assert!(!exchange.may_list(&listing_authority, &mint));
+        exchange.listing_program = listing_program;
+        assert!(exchange.may_list(&listing_authority, &mint));
+        assert!(!exchange.may_list(&listing_authority, &other_mint));
+    }
+
+    /// A book whose tick is worth two whole quote tokens
+    fn splittable() -> OrderBook {
+        OrderBook {
+            quote_lot_size: 1,
+            tick_size: 2 * PRICE_SCALE,
+            min_order_size: 5,
+            reference_price: 40 * PRICE_SCALE,
+            last_price: 42 * PRICE_SCALE,
+            open_orders_count: 3,
+            trigger_order_count: 2,
+            ..blank(OrderBook::LEN)
+        }
+    }
+
+    #[test]
+    fn a_split_restates_the_book_and_counts_accounts_to_rescale() {
+        let mut book = splittable();
+        book.restate(2).unwrap();
+
+        assert_eq!(book.tick_size, PRICE_SCALE);
+        assert_eq!(book.min_order_size, 10);
+        assert_eq!(book.reference_price, 20 * PRICE_SCALE);
+        assert_eq!(book.last_price, 21 * PRICE_SCALE);
+        assert_eq!(book.share_epoch, 1);
+        assert_eq!(book.share_multiplier, 2);
+        assert_eq!(book.pending_rescales, 5);
+        assert!(book.is_restating());
+    }
+
+    #[test]
+    fn a_split_must_leave_a_valid_tick() {
+        for multiplier in [0, 1, 3, 4] {
+            let mut book = splittable();
+            assert!(book.restate(multiplier).is_err(), "{multiplier}");
+            assert_eq!(book.share_epoch, 0);
+        }
+    }
+
+    #[test]
+    fn resting_orders_keep_their_value_through_a_split() {
+        let mut order = OrderNode {
+            quantity: 10,
+            filled_quantity: 4,
+            ..node(1, 6 * PRICE_SCALE)
+        };
+        order.rescale(3).unwrap();
+
+        assert_eq!(order.price, 2 * PRICE_SCALE);
+        assert_eq!((order.quantity, order.filled_quantity), (30, 12));
+
+        order.quantity = u64::MAX;
+        assert!(order.rescale(2).is_err());
+    }
+
+    #[test]
+    fn rescaling_multiplies_base_balances_only() {
+        let mut open_orders = OpenOrders {
+            base_free: 7,
+            base_locked: 3,
+            quote_free: 50,
+            quote_locked: 20,
+            ..blank(OpenOrders::LEN)
+        };
+        open_orders.rescale(2, 1).unwrap();
+
+        assert_eq!((open_orders.base_free, open_orders.base_locked), (14, 6));
+        assert_eq!((open_orders.quote_free, open_orders.quote_locked), (50, 20));
+        assert_eq!(open_orders.share_epoch, 1);
+    }
+
+    #[test]
+    fn only_ask_triggers_lock_more_after_a_split() {
+        let bid = TriggerOrder {
+            side: OrderSide::Bid,
+            trigger_price: 10 * PRICE_SCALE,
+            limit_price: 12 * PRICE_SCALE,
+            quantity: 5,
+            locked_amount: 600,
+            ..blank(TriggerOrder::LEN)
+        };
+        let ask = TriggerOrder {
+            side: OrderSide::Ask,
+            ..bid.clone()
+        };
+
+        for (mut trigger, locked) in [(bid, 600), (ask, 1_200)] {
+            trigger.rescale(2, 1).unwrap();
+            assert_eq!(trigger.trigger_price, 5 * PRICE_SCALE);
+            assert_eq!(trigger.limit_price, 6 * PRICE_SCALE);
+            assert_eq!(trigger.quantity, 10);
+            assert_eq!(trigger.locked_amount, locked);
+            assert_eq!(trigger.share_epoch, 1);
+        }
+    }
+
+    #[test]
+    fn kyc_is_valid_only_while_verified_and_unexpired() {
+        let verified = KycEntry {
+            status: KycStatus::Verified,
+            expires_at: 1_000,
+            ..blank(KycEntry::LEN)
+        };
+        assert!(verified.is_valid(999));
+        assert!(!verified.is_valid(1_000));
+
+        for status in [KycStatus::Suspended, KycStatus::Revoked] {
+            let blocked = KycEntry {
+                status,
+                ..verified.clone()
+            };
+            assert!(!blocked.is_valid(0));
+        }
+    }
 }
@@ -0,0 +1,87 @@
+use crate::constants::*;
+use crate::error::ExchangeError;
+use crate::state::*;
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
+
+/// Transfer tokens out of one of the book's vaults, signed by the order book PDA.
+/// `extra_accounts` are forwarded so Token-2022 transfer hooks can resolve theirs.
+pub fn release_tokens<'info>(
+    order_book: &Account<'info, OrderBook>,
+    vault: &InterfaceAccount<'info, TokenAccount>,
+    mint: &InterfaceAccount<'info, Mint>,
+    to: AccountInfo<'info>,
+    token_program: &Interface<'info, TokenInterface>,
+    extra_accounts: &[AccountInfo<'info>],
+    amount: u64,
+) -> Result<()> {
+    if amount == 0 {
+        return Ok(());
+    }
+
+    let signer_seeds: &[&[u8]] = &[
+        ORDER_BOOK_SEED,
+        order_book.base_mint.as_ref(),
+        order_book.quote_mint.as_ref(),
+        &[order_book.bump],
+    ];
+
+    token_interface::transfer_checked(
+        CpiContext::new_with_signer(
+            token_program.to_account_info(),
+            TransferChecked {
+                from: vault.to_account_info(),
+                mint: mint.to_account_info(),
+                to,
+                authority: order_book.to_account_info(),
+            },
+            &[signer_seeds],
+        )
+        .with_remaining_accounts(extra_accounts.to_vec()),
+        amount,
+        mint.decimals,
+    )
+}
+
+/// Move lamports out of an account owned by this program
+pub fn move_lamports(from: &AccountInfo, to: &AccountInfo, amount: u64) -> Result<()> {
+    if amount == 0 {
+        return Ok(());
+    }
+
+    **from.try_borrow_mut_lamports()? = from
+        .lamports()
+        .checked_sub(amount)
+        .ok_or(ExchangeError::InsufficientFunds)?;
+    **to.try_borrow_mut_lamports()? = to
+        .lamports()
+        .checked_add(amount)
+        .ok_or(ExchangeError::Overflow)?;
+
+    Ok(())
+}
+
+/// Unlock `amount` that a removed order still had locked back into its owner's
+/// free balance. `open_orders` must be the owner's account on this book.
+pub fn refund_order<'info>(
+    order_book: &Pubkey,
+    side: OrderSide,
+    owner: &Pubkey,
+    amount: u64,
+    open_orders: &'info AccountInfo<'info>,
+) -> Result<()> {
+    let mut open_orders = Account::<OpenOrders>::try_from(open_orders)?;
+    require_keys_eq!(
+        open_orders.owner,
+        *owner,
+        ExchangeError::InvalidRefundAccount
+    );
+    require_keys_eq!(
+        open_orders.order_book,
+        *order_book,
+        ExchangeError::InvalidRefundAccount
+    );
+
+    open_orders.unlock(side, amount)?;
+    open_orders.exit(&crate::ID)
+}
@@ -1,5 +1,5 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
 use crate::constants::*;
 use crate::error::FeeError;
 use crate::state::*;
@@ -21,16 +21,19 @@ pub struct ClaimReferralRewards<'info> {
     pub referral_account: Account<'info, ReferralAccount>,
     
     #[account(mut)]
-    pub fee_collector_account: Account<'info, TokenAccount>,
+    pub fee_collector_account: InterfaceAccount<'info, TokenAccount>,
     
     #[account(mut)]
-    pub referrer_token_account: Account<'info, TokenAccount>,
+    pub referrer_token_account: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(address = fee_collector_account.mint)]
+    pub mint: InterfaceAccount<'info, Mint>,
     
     pub referrer: Signer<'info>,
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
 }
 
-pub fn handler(ctx: Context<ClaimReferralRewards>) -> Result<()> {
+pub fn handler<'info>(ctx: Context<'_, '_, 'info, 'info, ClaimReferralRewards<'info>>) -> Result<()> {
     let referral_account = &mut ctx.accounts.referral_account;
     let unclaimed = referral_account.unclaimed_rewards();
     
@@ -40,18 +43,21 @@ pub fn handler(ctx: Context<ClaimReferralRewards>) -> Result<()> {
     let seeds = &[FEE_CONFIG_SEED, &[fee_config.bump]];
 
     // Transfer rewards to referrer
-    let cpi_accounts = Transfer {
+    let cpi_accounts = TransferChecked {
         from: ctx.accounts.fee_collector_account.to_account_info(),
+        mint: ctx.accounts.mint.to_account_info(),
         to: ctx.accounts.referrer_token_account.to_account_info(),
         authority: ctx.accounts.fee_config.to_account_info(),
     };
-    token::transfer(
+    token_interface::transfer_checked(
         CpiContext::new_with_signer(
             ctx.accounts.token_program.to_account_info(),
             cpi_accounts,
             &[seeds]
-        ),
-        unclaimed
+        )
+        .with_remaining_accounts(ctx.remaining_accounts.to_vec()),
+        unclaimed,
+        ctx.accounts.mint.decimals
     )?;
 
     referral_account.claimed_rewards = referral_account.claimed_rewards
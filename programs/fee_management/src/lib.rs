@@ -69,7 +69,9 @@ pub mod fee_management {
     }
 
     /// Claim referral rewards
-    pub fn claim_referral_rewards(ctx: Context<ClaimReferralRewards>) -> Result<()> {
+    pub fn claim_referral_rewards<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ClaimReferralRewards<'info>>,
+    ) -> Result<()> {
         instructions::claim_referral_rewards::handler(ctx)
     }
 
@@ -0,0 +1,10 @@
+// PDA Seeds
+pub const OFFER_SEED: &[u8] = b"offer";
+pub const APPLICATION_SEED: &[u8] = b"application";
+pub const VAULT_SEED: &[u8] = b"vault";
+
+// Constraints
+pub const MAX_BIDS_PER_APPLICATION: usize = 3;
+pub const MAX_PRICE_LEVELS: usize = 32; // ticks in an offer's price band
+pub const CATEGORY_COUNT: usize = 3;
+pub const BPS_DENOMINATOR: u64 = 10000;
@@ -0,0 +1,52 @@
+use anchor_lang::prelude::*;
+
+#[error_code]
+pub enum IpoError {
+    #[msg("Invalid lot size, price band or tick size")]
+    InvalidOfferParams,
+
+    #[msg("Lot, tick or price band settings are not accepted by the exchange")]
+    InvalidMarketParams,
+
+    #[msg("Category quotas must add up to 100%")]
+    InvalidQuota,
+
+    #[msg("Bid window must end after it starts and in the future")]
+    InvalidBidWindow,
+
+    #[msg("Offer is not open for bidding")]
+    OfferNotOpen,
+
+    #[msg("Bid window is not open")]
+    BiddingClosed,
+
+    #[msg("Bid window has not closed yet")]
+    BiddingNotClosed,
+
+    #[msg("An application needs between one and three bids")]
+    InvalidBidCount,
+
+    #[msg("Bid quantity must be at least one lot")]
+    InvalidBidQuantity,
+
+    #[msg("Bid price is outside the price band or not on a tick")]
+    PriceOutsideBand,
+
+    #[msg("Application exceeds the retail investment limit")]
+    RetailLimitExceeded,
+
+    #[msg("Escrow received less than the application's cost, e.g. after a transfer fee")]
+    InsufficientDeposit,
+
+    #[msg("Offer has not been allotted")]
+    OfferNotAllotted,
+
+    #[msg("Investor is not KYC verified")]
+    InvestorNotVerified,
+
+    #[msg("Unauthorized access")]
+    Unauthorized,
+
+    #[msg("Overflow in calculation")]
+    Overflow,
+}
@@ -0,0 +1,38 @@
+use crate::error::IpoError;
+use crate::state::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct AllotOffer<'info> {
+    #[account(mut)]
+    pub offer: Account<'info, Offer>,
+
+    /// Anyone can run the allotment once bidding closes
+    pub cranker: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<AllotOffer>) -> Result<()> {
+    let offer = &mut ctx.accounts.offer;
+    require!(offer.status == OfferStatus::Open, IpoError::OfferNotOpen);
+    require!(
+        Clock::get()?.unix_timestamp >= offer.bid_end,
+        IpoError::BiddingNotClosed
+    );
+
+    offer.allot();
+    offer.status = OfferStatus::Allotted;
+
+    msg!(
+        "Offer for {} allotted at cut-off price {}: Retail {}/{}, QIB {}/{}, NII {}/{} lots",
+        offer.share_mint,
+        offer.cut_off_price,
+        offer.allotted_lots[0],
+        offer.eligible_lots[0],
+        offer.allotted_lots[1],
+        offer.eligible_lots[1],
+        offer.allotted_lots[2],
+        offer.eligible_lots[2]
+    );
+
+    Ok(())
+}
@@ -0,0 +1,114 @@
+use crate::constants::*;
+use crate::error::IpoError;
+use crate::state::*;
+use crate::vault::*;
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+#[derive(Accounts)]
+pub struct ClaimAllotment<'info> {
+    #[account(
+        mut,
+        has_one = share_vault,
+        has_one = share_mint,
+        has_one = quote_vault,
+        has_one = quote_mint,
+    )]
+    pub offer: Account<'info, Offer>,
+
+    #[account(
+        mut,
+        close = investor,
+        seeds = [APPLICATION_SEED, offer.key().as_ref(), investor.key().as_ref()],
+        bump = application.bump,
+    )]
+    pub application: Account<'info, Application>,
+
+    #[account(mut)]
+    pub share_vault: InterfaceAccount<'info, TokenAccount>,
+
+    pub share_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(mut)]
+    pub quote_vault: InterfaceAccount<'info, TokenAccount>,
+
+    pub quote_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        token::mint = share_mint,
+        token::authority = investor,
+    )]
+    pub investor_share_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        token::mint = quote_mint,
+        token::authority = investor,
+    )]
+    pub investor_quote_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// CHECK: Applicant, receives the application rent
+    #[account(mut)]
+    pub investor: AccountInfo<'info>,
+
+    /// Anyone can deliver allotments once the offer is allotted
+    pub cranker: Signer<'info>,
+
+    pub share_token_program: Interface<'info, TokenInterface>,
+    pub quote_token_program: Interface<'info, TokenInterface>,
+}
+
+/// Remaining accounts are forwarded to both transfers for Token-2022 transfer hooks
+pub fn handler<'info>(ctx: Context<'_, '_, 'info, 'info, ClaimAllotment<'info>>) -> Result<()> {
+    let offer = &ctx.accounts.offer;
+    require!(
+        offer.status != OfferStatus::Open,
+        IpoError::OfferNotAllotted
+    );
+
+    let application = &ctx.accounts.application;
+    let lots = offer.allotment_for(application);
+    let shares = lots.checked_mul(offer.lot_size).ok_or(IpoError::Overflow)?;
+    let cost = offer.cost(offer.cut_off_price, lots)?;
+    let refund = application
+        .locked_amount
+        .checked_sub(cost)
+        .ok_or(IpoError::Overflow)?;
+
+    release_tokens(
+        offer,
+        &ctx.accounts.share_vault,
+        &ctx.accounts.share_mint,
+        ctx.accounts.investor_share_account.to_account_info(),
+        &ctx.accounts.share_token_program,
+        ctx.remaining_accounts,
+        shares,
+    )?;
+    release_tokens(
+        offer,
+        &ctx.accounts.quote_vault,
+        &ctx.accounts.quote_mint,
+        ctx.accounts.investor_quote_account.to_account_info(),
+        &ctx.accounts.quote_token_program,
+        ctx.remaining_accounts,
+        refund,
+    )?;
+
+    let offer = &mut ctx.accounts.offer;
+    offer.proceeds = offer.proceeds.checked_add(cost).ok_or(IpoError::Overflow)?;
+    offer.open_applications = offer
+        .open_applications
+        .checked_sub(1)
+        .ok_or(IpoError::Overflow)?;
+
+    msg!(
+        "Allotment for {}: {} lots at {}, {} refunded",
+        application.investor,
+        lots,
+        offer.cut_off_price,
+        refund
+    );
+
+    Ok(())
+}
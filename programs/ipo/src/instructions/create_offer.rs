@@ -0,0 +1,166 @@
+use crate::constants::*;
+use crate::error::IpoError;
+use crate::state::*;
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program_option::COption;
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
+use exchange_core::state::MarketParams;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct OfferParams {
+    pub lot_size: u64, // shares per lot
+    pub total_lots: u64,
+    pub price_floor: u64, // quote tokens per share
+    pub price_cap: u64,
+    pub tick_size: u64,
+    pub market: MarketParams, // order book settings once the shares list
+    pub quotas_bps: [u16; CATEGORY_COUNT], // retail, QIB, NII
+    pub retail_max_amount: u64,
+    pub bid_start: i64,
+    pub bid_end: i64,
+}
+
+#[derive(Accounts)]
+pub struct CreateOffer<'info> {
+    #[account(
+        init,
+        payer = issuer,
+        space = Offer::LEN,
+        seeds = [OFFER_SEED, share_mint.key().as_ref()],
+        bump
+    )]
+    pub offer: Account<'info, Offer>,
+
+    /// Only the issuer of the shares may offer them
+    #[account(
+        constraint = share_mint.mint_authority == COption::Some(issuer.key()) @ IpoError::Unauthorized,
+    )]
+    pub share_mint: InterfaceAccount<'info, Mint>,
+
+    pub quote_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        init,
+        payer = issuer,
+        token::mint = share_mint,
+        token::authority = offer,
+        token::token_program = share_token_program,
+        seeds = [VAULT_SEED, offer.key().as_ref(), b"shares"],
+        bump
+    )]
+    pub share_vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        init,
+        payer = issuer,
+        token::mint = quote_mint,
+        token::authority = offer,
+        token::token_program = quote_token_program,
+        seeds = [VAULT_SEED, offer.key().as_ref(), b"quote"],
+        bump
+    )]
+    pub quote_vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        token::mint = share_mint,
+        token::authority = issuer,
+    )]
+    pub issuer_share_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub issuer: Signer<'info>,
+
+    pub share_token_program: Interface<'info, TokenInterface>,
+    pub quote_token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, CreateOffer<'info>>,
+    params: OfferParams,
+) -> Result<()> {
+    require!(
+        params.lot_size > 0
+            && params.total_lots > 0
+            && params.price_floor > 0
+            && params.tick_size > 0
+            && params.price_floor <= params.price_cap
+            && (params.price_cap - params.price_floor).is_multiple_of(params.tick_size),
+        IpoError::InvalidOfferParams
+    );
+    let levels = (params.price_cap - params.price_floor) / params.tick_size + 1;
+    require!(
+        levels <= MAX_PRICE_LEVELS as u64,
+        IpoError::InvalidOfferParams
+    );
+    require!(params.market.is_valid(), IpoError::InvalidMarketParams);
+    require!(
+        params.quotas_bps.iter().map(|&bps| bps as u64).sum::<u64>() == BPS_DENOMINATOR,
+        IpoError::InvalidQuota
+    );
+    let now = Clock::get()?.unix_timestamp;
+    require!(
+        params.bid_start < params.bid_end && params.bid_end > now,
+        IpoError::InvalidBidWindow
+    );
+
+    let offer = &mut ctx.accounts.offer;
+    offer.issuer = ctx.accounts.issuer.key();
+    offer.share_mint = ctx.accounts.share_mint.key();
+    offer.share_vault = ctx.accounts.share_vault.key();
+    offer.quote_mint = ctx.accounts.quote_mint.key();
+    offer.quote_vault = ctx.accounts.quote_vault.key();
+    offer.lot_size = params.lot_size;
+    offer.total_lots = params.total_lots;
+    offer.price_floor = params.price_floor;
+    offer.price_cap = params.price_cap;
+    offer.tick_size = params.tick_size;
+    offer.market = params.market;
+    offer.quotas_bps = params.quotas_bps;
+    offer.retail_max_amount = params.retail_max_amount;
+    offer.bid_start = params.bid_start;
+    offer.bid_end = params.bid_end;
+    offer.demand = vec![DemandLevel::default(); levels as usize];
+    offer.cut_off_price = 0;
+    offer.eligible_lots = [0; CATEGORY_COUNT];
+    offer.allotted_lots = [0; CATEGORY_COUNT];
+    offer.open_applications = 0;
+    offer.proceeds = 0;
+    offer.order_book = Pubkey::default();
+    offer.status = OfferStatus::Open;
+    offer.bump = ctx.bumps.offer;
+
+    // Escrow the whole issue; unsold shares go back to the issuer after allotment
+    let shares = params
+        .total_lots
+        .checked_mul(params.lot_size)
+        .ok_or(IpoError::Overflow)?;
+    token_interface::transfer_checked(
+        CpiContext::new(
+            ctx.accounts.share_token_program.to_account_info(),
+            TransferChecked {
+                from: ctx.accounts.issuer_share_account.to_account_info(),
+                mint: ctx.accounts.share_mint.to_account_info(),
+                to: ctx.accounts.share_vault.to_account_info(),
+                authority: ctx.accounts.issuer.to_account_info(),
+            },
+        )
+        .with_remaining_accounts(ctx.remaining_accounts.to_vec()),
+        shares,
+        ctx.accounts.share_mint.decimals,
+    )?;
+
+    msg!(
+        "Offer opened for {}: {} lots of {} shares, price band {}-{}, bidding {} to {}",
+        offer.share_mint,
+        params.total_lots,
+        params.lot_size,
+        params.price_floor,
+        params.price_cap,
+        params.bid_start,
+        params.bid_end
+    );
+
+    Ok(())
+}
@@ -0,0 +1,119 @@
+use crate::error::IpoError;
+use crate::state::*;
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenInterface};
+use exchange_core::constants::LISTING_AUTHORITY_SEED;
+use exchange_core::cpi::accounts::InitializeOrderBook;
+use exchange_core::program::ExchangeCore;
+
+#[derive(Accounts)]
+pub struct ListOffer<'info> {
+    #[account(
+        mut,
+        has_one = share_mint,
+        has_one = quote_mint,
+    )]
+    pub offer: Account<'info, Offer>,
+
+    /// CHECK: Signs the listing. Offers are unique per share mint, so this is the
+    /// offer's own signer; the exchange only accepts it for markets on that mint.
+    #[account(seeds = [LISTING_AUTHORITY_SEED, share_mint.key().as_ref()], bump)]
+    pub listing_authority: UncheckedAccount<'info>,
+
+    /// CHECK: Exchange config, validated by exchange_core
+    #[account(mut)]
+    pub exchange: UncheckedAccount<'info>,
+
+    /// CHECK: Order book PDA, created by exchange_core
+    #[account(mut)]
+    pub order_book: UncheckedAccount<'info>,
+
+    pub share_mint: InterfaceAccount<'info, Mint>,
+
+    pub quote_mint: InterfaceAccount<'info, Mint>,
+
+    /// CHECK: Base vault PDA, created by exchange_core
+    #[account(mut)]
+    pub base_vault: UncheckedAccount<'info>,
+
+    /// CHECK: Quote vault PDA, created by exchange_core
+    #[account(mut)]
+    pub quote_vault: UncheckedAccount<'info>,
+
+    /// CHECK: Bids slab PDA, created by exchange_core
+    #[account(mut)]
+    pub bids: UncheckedAccount<'info>,
+
+    /// CHECK: Asks slab PDA, created by exchange_core
+    #[account(mut)]
+    pub asks: UncheckedAccount<'info>,
+
+    /// CHECK: Event queue PDA, created by exchange_core
+    #[account(mut)]
+    pub event_queue: UncheckedAccount<'info>,
+
+    /// Anyone can list an allotted offer, paying for the market accounts
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub exchange_program: Program<'info, ExchangeCore>,
+    pub share_token_program: Interface<'info, TokenInterface>,
+    pub quote_token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+/// The market takes the settings fixed when the offer opened, and its price
+/// band is centred on the cut-off so trading opens around the issue price.
+pub fn handler(ctx: Context<ListOffer>) -> Result<()> {
+    let offer = &ctx.accounts.offer;
+    require!(
+        offer.status == OfferStatus::Allotted,
+        IpoError::OfferNotAllotted
+    );
+    let market = offer.market;
+    let reference_price = offer.listing_price()?;
+
+    let share_mint = ctx.accounts.share_mint.key();
+    let bump = [ctx.bumps.listing_authority];
+    let signer_seeds: &[&[&[u8]]] = &[&[LISTING_AUTHORITY_SEED, share_mint.as_ref(), &bump]];
+    exchange_core::cpi::initialize_order_book(
+        CpiContext::new_with_signer(
+            ctx.accounts.exchange_program.to_account_info(),
+            InitializeOrderBook {
+                exchange: ctx.accounts.exchange.to_account_info(),
+                order_book: ctx.accounts.order_book.to_account_info(),
+                base_mint: ctx.accounts.share_mint.to_account_info(),
+                quote_mint: ctx.accounts.quote_mint.to_account_info(),
+                base_vault: ctx.accounts.base_vault.to_account_info(),
+                quote_vault: ctx.accounts.quote_vault.to_account_info(),
+                bids: ctx.accounts.bids.to_account_info(),
+                asks: ctx.accounts.asks.to_account_info(),
+                event_queue: ctx.accounts.event_queue.to_account_info(),
+                authority: ctx.accounts.listing_authority.to_account_info(),
+                payer: ctx.accounts.payer.to_account_info(),
+                base_token_program: ctx.accounts.share_token_program.to_account_info(),
+                quote_token_program: ctx.accounts.quote_token_program.to_account_info(),
+                system_program: ctx.accounts.system_program.to_account_info(),
+                rent: ctx.accounts.rent.to_account_info(),
+            },
+            signer_seeds,
+        ),
+        share_mint,
+        market,
+        reference_price,
+    )?;
+
+    let offer = &mut ctx.accounts.offer;
+    offer.order_book = ctx.accounts.order_book.key();
+    offer.status = OfferStatus::Listed;
+
+    msg!(
+        "Offer for {} listed on order book {} with reference price {}",
+        offer.share_mint,
+        offer.order_book,
+        reference_price
+    );
+
+    Ok(())
+}
@@ -0,0 +1,34 @@
+pub mod allot_offer;
+pub mod claim_allotment;
+pub mod create_offer;
+pub mod list_offer;
+pub mod submit_application;
+pub mod withdraw_application;
+pub mod withdraw_proceeds;
+
+pub use allot_offer::AllotOffer;
+pub use claim_allotment::ClaimAllotment;
+pub use create_offer::{CreateOffer, OfferParams};
+pub use list_offer::ListOffer;
+pub use submit_application::SubmitApplication;
+pub use withdraw_application::WithdrawApplication;
+pub use withdraw_proceeds::WithdrawProceeds;
+
+// Only the accounts structs are re-exported, as every module has a `handler`;
+// `#[program]` also looks for the client modules their derive generates here
+pub(crate) use allot_offer::__client_accounts_allot_offer;
+pub(crate) use claim_allotment::__client_accounts_claim_allotment;
+pub(crate) use create_offer::__client_accounts_create_offer;
+pub(crate) use list_offer::__client_accounts_list_offer;
+pub(crate) use submit_application::__client_accounts_submit_application;
+pub(crate) use withdraw_application::__client_accounts_withdraw_application;
+pub(crate) use withdraw_proceeds::__client_accounts_withdraw_proceeds;
+#[cfg(feature = "cpi")]
+pub(crate) use {
+    allot_offer::__cpi_client_accounts_allot_offer,
+    claim_allotment::__cpi_client_accounts_claim_allotment,
+    create_offer::__cpi_client_accounts_create_offer, list_offer::__cpi_client_accounts_list_offer,
+    submit_application::__cpi_client_accounts_submit_application,
+    withdraw_application::__cpi_client_accounts_withdraw_application,
+    withdraw_proceeds::__cpi_client_accounts_withdraw_proceeds,
+};
@@ -0,0 +1,141 @@
+use crate::constants::*;
+use crate::error::IpoError;
+use crate::state::*;
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
+use exchange_core::constants::KYC_ENTRY_SEED;
+use exchange_core::state::KycEntry;
+
+#[derive(Accounts)]
+pub struct SubmitApplication<'info> {
+    #[account(
+        mut,
+        has_one = quote_vault,
+        has_one = quote_mint,
+    )]
+    pub offer: Account<'info, Offer>,
+
+    #[account(
+        init,
+        payer = investor,
+        space = Application::LEN,
+        seeds = [APPLICATION_SEED, offer.key().as_ref(), investor.key().as_ref()],
+        bump
+    )]
+    pub application: Account<'info, Application>,
+
+    #[account(mut)]
+    pub quote_vault: InterfaceAccount<'info, TokenAccount>,
+
+    pub quote_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        token::mint = quote_mint,
+        token::authority = investor,
+    )]
+    pub investor_quote_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// The investor's exchange KYC entry, which fixes their category
+    #[account(
+        seeds = [KYC_ENTRY_SEED, investor.key().as_ref()],
+        seeds::program = exchange_core::ID,
+        bump = kyc_entry.bump,
+    )]
+    pub kyc_entry: Account<'info, KycEntry>,
+
+    #[account(mut)]
+    pub investor: Signer<'info>,
+
+    pub quote_token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, SubmitApplication<'info>>,
+    bids: Vec<Bid>,
+) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let offer = &ctx.accounts.offer;
+    require!(offer.status == OfferStatus::Open, IpoError::OfferNotOpen);
+    require!(offer.is_bidding(now), IpoError::BiddingClosed);
+    require!(
+        ctx.accounts.kyc_entry.is_valid(now),
+        IpoError::InvestorNotVerified
+    );
+    let category = InvestorCategory::from(ctx.accounts.kyc_entry.category);
+    require!(
+        !bids.is_empty() && bids.len() <= MAX_BIDS_PER_APPLICATION,
+        IpoError::InvalidBidCount
+    );
+
+    // Lock enough for the most expensive alternative
+    let mut locked_amount = 0;
+    for bid in &bids {
+        require!(
+            bid.lots > 0 && bid.lots <= offer.total_lots,
+            IpoError::InvalidBidQuantity
+        );
+        require!(
+            offer.level_index(bid.price).is_some(),
+            IpoError::PriceOutsideBand
+        );
+        locked_amount = locked_amount.max(offer.cost(bid.price, bid.lots)?);
+    }
+    if category == InvestorCategory::Retail && offer.retail_max_amount > 0 {
+        require!(
+            locked_amount <= offer.retail_max_amount,
+            IpoError::RetailLimitExceeded
+        );
+    }
+
+    // Record what the escrow received, which a transfer fee would reduce
+    let balance_before = ctx.accounts.quote_vault.amount;
+    token_interface::transfer_checked(
+        CpiContext::new(
+            ctx.accounts.quote_token_program.to_account_info(),
+            TransferChecked {
+                from: ctx.accounts.investor_quote_account.to_account_info(),
+                mint: ctx.accounts.quote_mint.to_account_info(),
+                to: ctx.accounts.quote_vault.to_account_info(),
+                authority: ctx.accounts.investor.to_account_info(),
+            },
+        )
+        .with_remaining_accounts(ctx.remaining_accounts.to_vec()),
+        locked_amount,
+        ctx.accounts.quote_mint.decimals,
+    )?;
+    ctx.accounts.quote_vault.reload()?;
+    let received = ctx
+        .accounts
+        .quote_vault
+        .amount
+        .checked_sub(balance_before)
+        .ok_or(IpoError::Overflow)?;
+    require!(received >= locked_amount, IpoError::InsufficientDeposit);
+
+    let application = &mut ctx.accounts.application;
+    application.offer = ctx.accounts.offer.key();
+    application.investor = ctx.accounts.investor.key();
+    application.category = category;
+    application.bids = bids;
+    application.locked_amount = received;
+    application.bump = ctx.bumps.application;
+
+    let offer = &mut ctx.accounts.offer;
+    offer.record_demand(application, true)?;
+    offer.open_applications = offer
+        .open_applications
+        .checked_add(1)
+        .ok_or(IpoError::Overflow)?;
+
+    msg!(
+        "Application from {} ({:?}): {} bids, {} locked",
+        application.investor,
+        category,
+        application.bids.len(),
+        received
+    );
+
+    Ok(())
+}
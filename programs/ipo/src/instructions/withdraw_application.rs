@@ -0,0 +1,76 @@
+use crate::constants::*;
+use crate::error::IpoError;
+use crate::state::*;
+use crate::vault::*;
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+#[derive(Accounts)]
+pub struct WithdrawApplication<'info> {
+    #[account(
+        mut,
+        has_one = quote_vault,
+        has_one = quote_mint,
+    )]
+    pub offer: Account<'info, Offer>,
+
+    #[account(
+        mut,
+        close = investor,
+        seeds = [APPLICATION_SEED, offer.key().as_ref(), investor.key().as_ref()],
+        bump = application.bump,
+    )]
+    pub application: Account<'info, Application>,
+
+    #[account(mut)]
+    pub quote_vault: InterfaceAccount<'info, TokenAccount>,
+
+    pub quote_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        token::mint = quote_mint,
+        token::authority = investor,
+    )]
+    pub investor_quote_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub investor: Signer<'info>,
+
+    pub quote_token_program: Interface<'info, TokenInterface>,
+}
+
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, WithdrawApplication<'info>>,
+) -> Result<()> {
+    let offer = &mut ctx.accounts.offer;
+    require!(
+        offer.is_bidding(Clock::get()?.unix_timestamp),
+        IpoError::BiddingClosed
+    );
+
+    let application = &ctx.accounts.application;
+    offer.record_demand(application, false)?;
+    offer.open_applications = offer
+        .open_applications
+        .checked_sub(1)
+        .ok_or(IpoError::Overflow)?;
+
+    release_tokens(
+        &ctx.accounts.offer,
+        &ctx.accounts.quote_vault,
+        &ctx.accounts.quote_mint,
+        ctx.accounts.investor_quote_account.to_account_info(),
+        &ctx.accounts.quote_token_program,
+        ctx.remaining_accounts,
+        application.locked_amount,
+    )?;
+
+    msg!(
+        "Application from {} withdrawn, {} refunded",
+        application.investor,
+        application.locked_amount
+    );
+
+    Ok(())
+}
@@ -0,0 +1,92 @@
+use crate::error::IpoError;
+use crate::state::*;
+use crate::vault::*;
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+#[derive(Accounts)]
+pub struct WithdrawProceeds<'info> {
+    #[account(
+        mut,
+        has_one = issuer @ IpoError::Unauthorized,
+        has_one = share_vault,
+        has_one = share_mint,
+        has_one = quote_vault,
+        has_one = quote_mint,
+    )]
+    pub offer: Account<'info, Offer>,
+
+    #[account(mut)]
+    pub share_vault: InterfaceAccount<'info, TokenAccount>,
+
+    pub share_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(mut)]
+    pub quote_vault: InterfaceAccount<'info, TokenAccount>,
+
+    pub quote_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        token::mint = share_mint,
+        token::authority = issuer,
+    )]
+    pub issuer_share_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        token::mint = quote_mint,
+        token::authority = issuer,
+    )]
+    pub issuer_quote_account: InterfaceAccount<'info, TokenAccount>,
+
+    pub issuer: Signer<'info>,
+
+    pub share_token_program: Interface<'info, TokenInterface>,
+    pub quote_token_program: Interface<'info, TokenInterface>,
+}
+
+pub fn handler<'info>(ctx: Context<'_, '_, 'info, 'info, WithdrawProceeds<'info>>) -> Result<()> {
+    let offer = &ctx.accounts.offer;
+    require!(
+        offer.status != OfferStatus::Open,
+        IpoError::OfferNotAllotted
+    );
+
+    let proceeds = offer.proceeds;
+    release_tokens(
+        offer,
+        &ctx.accounts.quote_vault,
+        &ctx.accounts.quote_mint,
+        ctx.accounts.issuer_quote_account.to_account_info(),
+        &ctx.accounts.quote_token_program,
+        ctx.remaining_accounts,
+        proceeds,
+    )?;
+
+    // Whatever no application can still claim was not sold
+    let unsold = if offer.open_applications == 0 {
+        ctx.accounts.share_vault.amount
+    } else {
+        0
+    };
+    release_tokens(
+        offer,
+        &ctx.accounts.share_vault,
+        &ctx.accounts.share_mint,
+        ctx.accounts.issuer_share_account.to_account_info(),
+        &ctx.accounts.share_token_program,
+        ctx.remaining_accounts,
+        unsold,
+    )?;
+
+    ctx.accounts.offer.proceeds = 0;
+
+    msg!(
+        "Issuer withdrew {} proceeds and {} unsold shares",
+        proceeds,
+        unsold
+    );
+
+    Ok(())
+}
@@ -0,0 +1,66 @@
+pub mod constants;
+pub mod error;
+pub mod instructions;
+pub mod state;
+pub mod vault;
+
+use anchor_lang::prelude::*;
+use instructions::*;
+use state::Bid;
+
+declare_id!("BkbjbACutG5nAV98dRaEx2gLZenmtm5xga9CTzNzA6H2");
+
+#[program]
+pub mod ipo {
+    use super::*;
+
+    /// Open a book-built offer, escrowing the shares on offer from the issuer (mint authority only)
+    pub fn create_offer<'info>(
+        ctx: Context<'_, '_, 'info, 'info, CreateOffer<'info>>,
+        params: OfferParams,
+    ) -> Result<()> {
+        instructions::create_offer::handler(ctx, params)
+    }
+
+    /// Apply with up to three alternative bids, locking enough quote for the largest.
+    /// The investor's category comes from their exchange KYC entry.
+    pub fn submit_application<'info>(
+        ctx: Context<'_, '_, 'info, 'info, SubmitApplication<'info>>,
+        bids: Vec<Bid>,
+    ) -> Result<()> {
+        instructions::submit_application::handler(ctx, bids)
+    }
+
+    /// Withdraw an application during the bid window and unlock its funds
+    pub fn withdraw_application<'info>(
+        ctx: Context<'_, '_, 'info, 'info, WithdrawApplication<'info>>,
+    ) -> Result<()> {
+        instructions::withdraw_application::handler(ctx)
+    }
+
+    /// Fix the cut-off price and category allotments once bidding closes (permissionless)
+    pub fn allot_offer(ctx: Context<AllotOffer>) -> Result<()> {
+        instructions::allot_offer::handler(ctx)
+    }
+
+    /// Deliver an application's allotted shares and refund the rest of its funds (permissionless)
+    pub fn claim_allotment<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ClaimAllotment<'info>>,
+    ) -> Result<()> {
+        instructions::claim_allotment::handler(ctx)
+    }
+
+    /// Pay the issuer the proceeds of claimed allotments and, once every
+    /// application is claimed, return unsold shares
+    pub fn withdraw_proceeds<'info>(
+        ctx: Context<'_, '_, 'info, 'info, WithdrawProceeds<'info>>,
+    ) -> Result<()> {
+        instructions::withdraw_proceeds::handler(ctx)
+    }
+
+    /// List the allotted shares on the exchange by creating their order book,
+    /// with its price band centred on the cut-off (permissionless)
+    pub fn list_offer(ctx: Context<ListOffer>) -> Result<()> {
+        instructions::list_offer::handler(ctx)
+    }
+}
@@ -0,0 +1,440 @@
+use crate::constants::*;
+use crate::error::IpoError;
+use anchor_lang::prelude::*;
+use exchange_core::constants::PRICE_SCALE;
+use exchange_core::state::MarketParams;
+
+/// A book-built issue: investors bid within a price band during the bid window,
+/// then every successful bid is allotted at a single cut-off price.
+#[account]
+pub struct Offer {
+    pub issuer: Pubkey,
+    pub share_mint: Pubkey,
+    pub share_vault: Pubkey, // shares on offer, escrowed when the offer opens
+    pub quote_mint: Pubkey,
+    pub quote_vault: Pubkey, // funds locked by applications
+    pub lot_size: u64,       // shares per lot; bids are in lots
+    pub total_lots: u64,
+    pub price_floor: u64, // quote tokens per share
+    pub price_cap: u64,
+    pub tick_size: u64,
+    pub market: MarketParams, // settings the order book is created with on listing
+    pub quotas_bps: [u16; CATEGORY_COUNT], // share of the issue reserved per category
+    pub retail_max_amount: u64, // most a retail application may lock, 0 for no limit
+    pub bid_start: i64,
+    pub bid_end: i64,
+    pub demand: Vec<DemandLevel>, // one per tick from the floor to the cap
+    pub cut_off_price: u64,       // set at allotment
+    pub eligible_lots: [u64; CATEGORY_COUNT], // demand at or above the cut-off
+    pub allotted_lots: [u64; CATEGORY_COUNT], // lots shared out pro rata within each category
+    pub open_applications: u32,
+    pub proceeds: u64,      // paid by claimed allotments and not yet withdrawn
+    pub order_book: Pubkey, // exchange market once listed
+    pub status: OfferStatus,
+    pub bump: u8,
+}
+
+impl Offer {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // issuer
+        32 + // share_mint
+        32 + // share_vault
+        32 + // quote_mint
+        32 + // quote_vault
+        8 + // lot_size
+        8 + // total_lots
+        8 + // price_floor
+        8 + // price_cap
+        8 + // tick_size
+        MarketParams::LEN + // market
+        2 * CATEGORY_COUNT + // quotas_bps
+        8 + // retail_max_amount
+        8 + // bid_start
+        8 + // bid_end
+        4 + DemandLevel::LEN * MAX_PRICE_LEVELS + // demand
+        8 + // cut_off_price
+        8 * CATEGORY_COUNT + // eligible_lots
+        8 * CATEGORY_COUNT + // allotted_lots
+        4 + // open_applications
+        8 + // proceeds
+        32 + // order_book
+        1 + // status
+        1; // bump
+
+    pub fn is_bidding(&self, now: i64) -> bool {
+        self.status == OfferStatus::Open && now >= self.bid_start && now < self.bid_end
+    }
+
+    /// Demand level a bid at `price` falls on, if it is a tick inside the band
+    pub fn level_index(&self, price: u64) -> Option<usize> {
+        if price < self.price_floor || price > self.price_cap {
+            return None;
+        }
+        let offset = price - self.price_floor;
+        if !offset.is_multiple_of(self.tick_size) {
+            return None;
+        }
+        Some((offset / self.tick_size) as usize)
+    }
+
+    pub fn level_price(&self, index: usize) -> u64 {
+        self.price_floor + self.tick_size * index as u64
+    }
+
+    /// Quote tokens needed for `lots` at `price` per share
+    pub fn cost(&self, price: u64, lots: u64) -> Result<u64> {
+        let cost = (price as u128)
+            .checked_mul(lots as u128)
+            .and_then(|value| value.checked_mul(self.lot_size as u128))
+            .ok_or(IpoError::Overflow)?;
+        Ok(u64::try_from(cost).map_err(|_| IpoError::Overflow)?)
+    }
+
+    /// Add or remove an application's bids from the demand curve. Only the
+    /// largest bid at or above a price counts there, so each level records
+    /// how much the application's demand grows at that price.
+    pub fn record_demand(&mut self, application: &Application, add: bool) -> Result<()> {
+        let category = application.category.index();
+        let mut above = 0;
+        for index in (0..self.demand.len()).rev() {
+            let demand = application.demand_at(self.level_price(index));
+            let level = &mut self.demand[index].lots[category];
+            *level = if add {
+                level.checked_add(demand - above)
+            } else {
+                level.checked_sub(demand - above)
+            }
+            .ok_or(IpoError::Overflow)?;
+            above = demand;
+        }
+        Ok(())
+    }
+
+    /// Lots bid at or above the demand level `index`, per category
+    pub fn demand_from(&self, index: usize) -> [u64; CATEGORY_COUNT] {
+        let mut lots = [0u64; CATEGORY_COUNT];
+        for level in &self.demand[index..] {
+            for (total, level_lots) in lots.iter_mut().zip(level.lots) {
+                *total = total.saturating_add(level_lots);
+            }
+        }
+        lots
+    }
+
+    /// Fix the cut-off price and how many lots each category receives.
+    ///
+    /// The cut-off is the highest price at which the issue is fully subscribed,
+    /// or the floor if it never is. Each category gets up to its quota; lots a
+    /// category cannot take spill over to the others' unmet demand.
+    pub fn allot(&mut self) {
+        let cut_off = (0..self.demand.len())
+            .rev()
+            .find(|&index| self.demand_from(index).iter().sum::<u64>() >= self.total_lots)
+            .unwrap_or(0);
+        self.cut_off_price = self.level_price(cut_off);
+        self.eligible_lots = self.demand_from(cut_off);
+
+        let mut remaining = self.total_lots;
+        for category in 0..CATEGORY_COUNT {
+            let quota = (self.total_lots as u128 * self.quotas_bps[category] as u128
+                / BPS_DENOMINATOR as u128) as u64;
+            self.allotted_lots[category] = quota.min(self.eligible_lots[category]);
+            remaining -= self.allotted_lots[category];
+        }
+        for category in 0..CATEGORY_COUNT {
+            let spill =
+                (self.eligible_lots[category] - self.allotted_lots[category]).min(remaining);
+            self.allotted_lots[category] += spill;
+            remaining -= spill;
+        }
+    }
+
+    /// Lots allotted to an application: its demand at the cut-off, scaled down
+    /// pro rata when its category is oversubscribed
+    pub fn allotment_for(&self, application: &Application) -> u64 {
+        let category = application.category.index();
+        let eligible = self.eligible_lots[category];
+        if eligible == 0 {
+            return 0;
+        }
+        let demand = application.demand_at(self.cut_off_price);
+        (demand as u128 * self.allotted_lots[category] as u128 / eligible as u128) as u64
+    }
+
+    /// The cut-off in order book units (quote lots per base lot with
+    /// `PRICE_DECIMALS` decimals), rounded down to the market's tick
+    pub fn listing_price(&self) -> Result<u64> {
+        let price = (self.cut_off_price as u128)
+            .checked_mul(self.market.base_lot_size as u128)
+            .and_then(|value| value.checked_mul(PRICE_SCALE as u128))
+            .ok_or(IpoError::Overflow)?
+            / self.market.quote_lot_size as u128;
+        let price = price - price % self.market.tick_size as u128;
+        Ok(u64::try_from(price).map_err(|_| IpoError::Overflow)?)
+    }
+
+    /// Signer seeds of the offer PDA, which owns both vaults
+    pub fn signer_seeds(&self) -> [&[u8]; 3] {
+        [
+            OFFER_SEED,
+            self.share_mint.as_ref(),
+            std::slice::from_ref(&self.bump),
+        ]
+    }
+}
+
+/// Lots bid at one price, per investor category
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default)]
+pub struct DemandLevel {
+    pub lots: [u64; CATEGORY_COUNT],
+}
+
+impl DemandLevel {
+    pub const LEN: usize = 8 * CATEGORY_COUNT;
+}
+
+/// An investor's bids on one offer. The bids are alternatives: the investor is
+/// allotted against the largest one at or above the cut-off and locks enough
+/// for the most expensive.
+#[account]
+pub struct Application {
+    pub offer: Pubkey,
+    pub investor: Pubkey,
+    pub category: InvestorCategory,
+    pub bids: Vec<Bid>,
+    pub locked_amount: u64,
+    pub bump: u8,
+}
+
+impl Application {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // offer
+        32 + // investor
+        1 + // category
+        4 + Bid::LEN * MAX_BIDS_PER_APPLICATION + // bids
+        8 + // locked_amount
+        1; // bump
+
+    /// Lots the investor would take at `price`
+    pub fn demand_at(&self, price: u64) -> u64 {
+        self.bids
+            .iter()
+            .filter(|bid| bid.price >= price)
+            .map(|bid| bid.lots)
+            .max()
+            .unwrap_or(0)
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+pub struct Bid {
+    pub price: u64, // quote tokens per share
+    pub lots: u64,
+}
+
+impl Bid {
+    pub const LEN: usize = 8 + 8;
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum InvestorCategory {
+    Retail, // retail individual investors
+    Qib,    // qualified institutional buyers
+    Nii,    // non-institutional investors
+}
+
+impl InvestorCategory {
+    pub fn index(self) -> usize {
+        match self {
+            InvestorCategory::Retail => 0,
+            InvestorCategory::Qib => 1,
+            InvestorCategory::Nii => 2,
+        }
+    }
+}
+
+/// Applications take the category the investor was verified under on the exchange
+impl From<exchange_core::state::InvestorCategory> for InvestorCategory {
+    fn from(category: exchange_core::state::InvestorCategory) -> Self {
+        match category {
+            exchange_core::state::InvestorCategory::Retail => InvestorCategory::Retail,
+            exchange_core::state::InvestorCategory::Institutional => InvestorCategory::Qib,
+            exchange_core::state::InvestorCategory::Professional => InvestorCategory::Nii,
+        }
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum OfferStatus {
+    Open,     // accepting bids until the window closes
+    Allotted, // cut-off fixed; applications can claim shares and refunds
+    Listed,   // trading on the exchange
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// An account with every field zeroed, `len` including the discriminator.
+    /// Vectors come out empty, leaving the rest of the space unread.
+    fn blank<T: AnchorDeserialize>(len: usize) -> T {
+        T::deserialize(&mut &vec![0; len - 8][..]).unwrap()
+    }
+
+    /// Ten lots of 100 shares bid from 100 to 102 a share; 35% retail, 50% QIB, 15% NII
+    fn offer() -> Offer {
+        let mut offer: Offer = blank(Offer::LEN);
+        offer.lot_size = 100;
+        offer.total_lots = 10;
+        offer.price_floor = 100;
+        offer.price_cap = 102;
+        offer.tick_size = 1;
+        offer.quotas_bps = [3_500, 5_000, 1_500];
+        offer.demand = vec![DemandLevel::default(); 3];
+        offer.market = MarketParams {
+            base_lot_size: 100,
+            quote_lot_size: 10,
+            tick_size: 1_000,
+            min_order_size: 1,
+            price_band_bps: 1_000,
+        };
+        offer
+    }
+
+    fn application(category: InvestorCategory, bids: &[(u64, u64)]) -> Application {
+        Application {
+            offer: Pubkey::new_unique(),
+            investor: Pubkey::new_unique(),
+            category,
+            bids: bids
+                .iter()
+                .map(|&(price, lots)| Bid { price, lots })
+                .collect(),
+            locked_amount: 0,
+            bump: 0,
+        }
+    }
+
+    #[test]
+    fn bid_prices_must_be_ticks_inside_the_band() {
+        let offer = Offer {
+            tick_size: 2,
+            price_cap: 104,
+            ..offer()
+        };
+
+        assert_eq!(offer.level_index(100), Some(0));
+        assert_eq!(offer.level_index(104), Some(2));
+        assert_eq!(offer.level_index(101), None);
+        assert_eq!(offer.level_index(99), None);
+        assert_eq!(offer.level_index(106), None);
+    }
+
+    #[test]
+    fn alternative_bids_count_the_largest_at_or_above_a_price() {
+        let application = application(InvestorCategory::Retail, &[(100, 10), (102, 2)]);
+
+        assert_eq!(application.demand_at(100), 10);
+        assert_eq!(application.demand_at(101), 2);
+        assert_eq!(application.demand_at(103), 0);
+    }
+
+    #[test]
+    fn withdrawing_an_application_removes_its_demand() {
+        let mut offer = offer();
+        let application = application(InvestorCategory::Qib, &[(100, 10), (102, 2)]);
+
+        offer.record_demand(&application, true).unwrap();
+        assert_eq!(offer.demand_from(0), [0, 10, 0]);
+        assert_eq!(offer.demand_from(2), [0, 2, 0]);
+
+        offer.record_demand(&application, false).unwrap();
+        assert_eq!(offer.demand_from(0), [0, 0, 0]);
+    }
+
+    #[test]
+    fn cut_off_is_the_highest_fully_subscribed_price() {
+        let mut offer = offer();
+        let retail = application(InvestorCategory::Retail, &[(102, 4)]);
+        let qib = application(InvestorCategory::Qib, &[(101, 8)]);
+        offer.record_demand(&retail, true).unwrap();
+        offer.record_demand(&qib, true).unwrap();
+
+        offer.allot();
+
+        assert_eq!(offer.cut_off_price, 101);
+        assert_eq!(offer.eligible_lots, [4, 8, 0]);
+        // Quotas of 3, 5 and 1 lots; the NII lot nobody bid for spills over
+        assert_eq!(offer.allotted_lots, [4, 6, 0]);
+        assert_eq!(offer.allotment_for(&retail), 4);
+        assert_eq!(offer.allotment_for(&qib), 6);
+    }
+
+    #[test]
+    fn undersubscribed_offers_allot_every_bid_at_the_floor() {
+        let mut offer = offer();
+        let retail = application(InvestorCategory::Retail, &[(102, 2)]);
+        let nii = application(InvestorCategory::Nii, &[(100, 3)]);
+        offer.record_demand(&retail, true).unwrap();
+        offer.record_demand(&nii, true).unwrap();
+
+        offer.allot();
+
+        assert_eq!(offer.cut_off_price, 100);
+        assert_eq!(offer.allotment_for(&retail), 2);
+        assert_eq!(offer.allotment_for(&nii), 3);
+    }
+
+    #[test]
+    fn oversubscribed_categories_are_scaled_down_pro_rata() {
+        let mut offer = Offer {
+            quotas_bps: [5_000, 5_000, 0],
+            ..offer()
+        };
+        let first = application(InvestorCategory::Retail, &[(100, 6)]);
+        let second = application(InvestorCategory::Retail, &[(100, 4)]);
+        let qib = application(InvestorCategory::Qib, &[(100, 20)]);
+        for application in [&first, &second, &qib] {
+            offer.record_demand(application, true).unwrap();
+        }
+
+        offer.allot();
+
+        assert_eq!(offer.allotted_lots, [5, 5, 0]);
+        assert_eq!(offer.allotment_for(&first), 3);
+        assert_eq!(offer.allotment_for(&second), 2);
+        assert_eq!(offer.allotment_for(&qib), 5);
+    }
+
+    #[test]
+    fn listing_price_converts_the_cut_off_to_order_book_units() {
+        let mut offer = Offer {
+            cut_off_price: 101,
+            ..offer()
+        };
+        // 101 a share is 10,100 quote tokens, or 1,010 quote lots, per base lot
+        assert_eq!(offer.listing_price().unwrap(), 1_010 * PRICE_SCALE);
+
+        offer.market.quote_lot_size = 3;
+        offer.market.tick_size = PRICE_SCALE;
+        assert_eq!(offer.listing_price().unwrap(), 3_366 * PRICE_SCALE);
+    }
+
+    #[test]
+    fn categories_follow_the_exchange_kyc_category() {
+        use exchange_core::state::InvestorCategory as KycCategory;
+
+        assert_eq!(
+            InvestorCategory::from(KycCategory::Retail),
+            InvestorCategory::Retail
+        );
+        assert_eq!(
+            InvestorCategory::from(KycCategory::Institutional),
+            InvestorCategory::Qib
+        );
+        assert_eq!(
+            InvestorCategory::from(KycCategory::Professional),
+            InvestorCategory::Nii
+        );
+    }
+}
@@ -0,0 +1,35 @@
+use crate::state::*;
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
+
+/// Transfer tokens out of one of the offer's vaults, signed by the offer PDA.
+/// `extra_accounts` are forwarded so Token-2022 transfer hooks can resolve theirs.
+pub fn release_tokens<'info>(
+    offer: &Account<'info, Offer>,
+    vault: &InterfaceAccount<'info, TokenAccount>,
+    mint: &InterfaceAccount<'info, Mint>,
+    to: AccountInfo<'info>,
+    token_program: &Interface<'info, TokenInterface>,
+    extra_accounts: &[AccountInfo<'info>],
+    amount: u64,
+) -> Result<()> {
+    if amount == 0 {
+        return Ok(());
+    }
+
+    token_interface::transfer_checked(
+        CpiContext::new_with_signer(
+            token_program.to_account_info(),
+            TransferChecked {
+                from: vault.to_account_info(),
+                mint: mint.to_account_info(),
+                to,
+                authority: offer.to_account_info(),
+            },
+            &[&offer.signer_seeds()],
+        )
+        .with_remaining_accounts(extra_accounts.to_vec()),
+        amount,
+        mint.decimals,
+    )
+}